@@ -66,7 +66,7 @@ What is the first frequency your device reaches twice?
 
 */
 
-use std::collections::HashMap;
+use std::collections::HashSet;
 use std::fs::File;
 use std::io::prelude::*;
 
@@ -85,49 +85,97 @@ fn get_frequencies() -> Vec<i32> {
   frequencies
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 struct FirstFreqReachedTwice {
-  val: i32,
+  val: i64,
   whole_list_round: usize,
   last_list_idx: usize,
 }
 
-fn get_first_freq_reached_twice(
-  frequencies: &[i32],
-) -> Result<FirstFreqReachedTwice, &'static str> {
-  let mut found_frequencies: HashMap<i32, bool> = HashMap::new();
-  let mut last_frequency = 0;
+impl FirstFreqReachedTwice {
+  // the frequency is reached again after applying `changes_num` changes
+  fn new(val: i64, changes_num: usize, list_len: usize) -> FirstFreqReachedTwice {
+    FirstFreqReachedTwice {
+      val,
+      whole_list_round: (changes_num - 1) / list_len,
+      last_list_idx: (changes_num - 1) % list_len,
+    }
+  }
+}
 
-  for whole_list_round in 0..1000 {
-    for (idx, frequency) in frequencies.iter().enumerate() {
-      found_frequencies.insert(last_frequency, true);
+// Each round visits the frequencies of the first one shifted by the drift (the sum of the list).
+// When there is no repeat in the first round, a repeat happens when a frequency of it reaches
+// another one after some rounds, so both have the same value modulo the drift. Only the closest
+// one in the direction of the drift can be the first repeat, so it is enough to sort them
+fn get_first_freq_reached_twice(frequencies: &[i32]) -> Option<FirstFreqReachedTwice> {
+  let list_len = frequencies.len();
+  // the frequency before each change of the first round
+  let mut starts: Vec<i64> = Vec::with_capacity(list_len);
+  let mut found_frequencies: HashSet<i64> = HashSet::with_capacity(list_len);
+  let mut last_frequency: i64 = 0;
+
+  for (idx, frequency) in frequencies.iter().enumerate() {
+    starts.push(last_frequency);
+    found_frequencies.insert(last_frequency);
+
+    last_frequency += i64::from(*frequency);
+
+    if found_frequencies.contains(&last_frequency) {
+      return Some(FirstFreqReachedTwice::new(
+        last_frequency,
+        idx + 1,
+        list_len,
+      ));
+    }
+  }
 
-      last_frequency += frequency;
+  let drift = last_frequency;
 
-      if found_frequencies.contains_key(&last_frequency) {
-        return Ok(FirstFreqReachedTwice {
-          val: last_frequency,
-          whole_list_round,
-          last_list_idx: idx,
-        });
-      }
-    }
+  // without a drift the first round ends at the starting frequency, so the list is empty
+  if drift == 0 {
+    return None;
   }
 
-  Err("Frequency not found")
+  let mut sorted_idxs: Vec<usize> = (0..list_len).collect();
+
+  sorted_idxs.sort_by_key(|idx| {
+    (
+      starts[*idx].rem_euclid(drift.abs()),
+      starts[*idx] * drift.signum(),
+    )
+  });
+
+  sorted_idxs
+    .windows(2)
+    .filter(|pair| {
+      starts[pair[0]].rem_euclid(drift.abs()) == starts[pair[1]].rem_euclid(drift.abs())
+    })
+    .map(|pair| {
+      let rounds = (starts[pair[1]] - starts[pair[0]]) / drift;
+
+      FirstFreqReachedTwice::new(
+        starts[pair[1]],
+        rounds as usize * list_len + pair[0],
+        list_len,
+      )
+    })
+    .min_by_key(|repeat| (repeat.whole_list_round, repeat.last_list_idx))
 }
 
 fn main() {
   let frequencies = get_frequencies();
   let final_frequency: i32 = frequencies.iter().sum();
   // same: let final_frequency = frequencies.iter().fold(0, |acc, val| acc + val);
-  let first_freq_reached_twice = get_first_freq_reached_twice(&frequencies).unwrap();
+  let first_freq_reached_twice =
+    get_first_freq_reached_twice(&frequencies).expect("No frequency is reached twice");
 
   println!("Results:");
   println!("- (1) Final frequency: {}", final_frequency);
   println!(
-    "- (2) First frequency used twice: {:?}",
-    first_freq_reached_twice.val
+    "- (2) First frequency used twice: {:?} (round {}, change {})",
+    first_freq_reached_twice.val,
+    first_freq_reached_twice.whole_list_round + 1,
+    first_freq_reached_twice.last_list_idx + 1
   );
 }
 
@@ -137,7 +185,7 @@ mod tests {
 
   #[test]
   fn get_first_freq_reached_twice_example_1() {
-    let frequencies = vec![1, -2, 3, 1, 1, -2];
+    let frequencies = [1, -2, 3, 1, 1, -2];
     let value = get_first_freq_reached_twice(&frequencies).unwrap();
 
     assert_eq!(2, value.val);
@@ -147,7 +195,7 @@ mod tests {
 
   #[test]
   fn get_first_freq_reached_twice_example_2() {
-    let frequencies = vec![-1, 1];
+    let frequencies = [-1, 1];
     let value = get_first_freq_reached_twice(&frequencies).unwrap();
 
     assert_eq!(0, value.val);
@@ -157,7 +205,7 @@ mod tests {
 
   #[test]
   fn get_first_freq_reached_twice_example_3() {
-    let frequencies = vec![3, 3, 4, -2, -4];
+    let frequencies = [3, 3, 4, -2, -4];
     let value = get_first_freq_reached_twice(&frequencies).unwrap();
 
     assert_eq!(10, value.val);
@@ -167,7 +215,7 @@ mod tests {
 
   #[test]
   fn get_first_freq_reached_twice_example_4() {
-    let frequencies = vec![-6, 3, 8, 5, -6];
+    let frequencies = [-6, 3, 8, 5, -6];
     let value = get_first_freq_reached_twice(&frequencies).unwrap();
 
     assert_eq!(5, value.val);
@@ -177,11 +225,79 @@ mod tests {
 
   #[test]
   fn get_first_freq_reached_twice_example_5() {
-    let frequencies = vec![7, 7, -2, -7, -4];
+    let frequencies = [7, 7, -2, -7, -4];
     let value = get_first_freq_reached_twice(&frequencies).unwrap();
 
     assert_eq!(14, value.val);
     assert_eq!(2, value.whole_list_round);
     assert_eq!(2, value.last_list_idx);
   }
+
+  #[test]
+  fn get_first_freq_reached_twice_without_repeat() {
+    assert_eq!(get_first_freq_reached_twice(&[]), None);
+    assert_eq!(get_first_freq_reached_twice(&[1, 2, 3]), None);
+    assert_eq!(get_first_freq_reached_twice(&[3, 3]), None);
+    assert_eq!(get_first_freq_reached_twice(&[-4, 1, -4]), None);
+  }
+
+  #[test]
+  fn get_first_freq_reached_twice_after_many_rounds() {
+    let value = get_first_freq_reached_twice(&[1_000_000_000, -999_999_999]).unwrap();
+
+    assert_eq!(
+      value,
+      FirstFreqReachedTwice {
+        val: 1_000_000_000,
+        whole_list_round: 999_999_999,
+        last_list_idx: 1,
+      }
+    );
+  }
+
+  // the previous implementation, which applies the changes until a frequency repeats
+  fn get_first_freq_reached_twice_by_simulation(
+    frequencies: &[i32],
+    max_rounds: usize,
+  ) -> Option<FirstFreqReachedTwice> {
+    let mut found_frequencies: HashSet<i64> = HashSet::new();
+    let mut last_frequency: i64 = 0;
+
+    for whole_list_round in 0..max_rounds {
+      for (idx, frequency) in frequencies.iter().enumerate() {
+        found_frequencies.insert(last_frequency);
+
+        last_frequency += i64::from(*frequency);
+
+        if found_frequencies.contains(&last_frequency) {
+          return Some(FirstFreqReachedTwice {
+            val: last_frequency,
+            whole_list_round,
+            last_list_idx: idx,
+          });
+        }
+      }
+    }
+
+    None
+  }
+
+  // every list of changes from -2 to 2 with up to 4 items
+  #[test]
+  fn get_first_freq_reached_twice_small_lists() {
+    for len in 1..=4 {
+      for code in 0..5_i32.pow(len) {
+        let frequencies: Vec<i32> = (0..len).map(|idx| code / 5_i32.pow(idx) % 5 - 2).collect();
+        // the frequencies of a round are less than 10 apart, so a repeat takes less than 10 rounds
+        let expected = get_first_freq_reached_twice_by_simulation(&frequencies, 10);
+
+        assert_eq!(
+          get_first_freq_reached_twice(&frequencies),
+          expected,
+          "{:?}",
+          frequencies
+        );
+      }
+    }
+  }
 }
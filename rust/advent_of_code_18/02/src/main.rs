@@ -78,6 +78,9 @@ What letters are common between the two correct box IDs? (In the example above,
 
 */
 
+mod near_duplicates;
+
+use near_duplicates::{find_near_duplicates, Distance};
 use std::collections::HashMap;
 use std::fs::File;
 use std::io::prelude::*;
@@ -138,63 +141,20 @@ fn get_checksum_of_ids(ids: &[String]) -> usize {
   strs_with_3_letters * strs_with_2_letters
 }
 
-fn get_diff_chars_in_str(a: &str, b: &str) -> usize {
-  let mut diff_chars = 0;
-
-  for (idx, a_char) in a.chars().enumerate() {
-    let mut b_chars = b.chars();
-
-    if a_char != b_chars.nth(idx).unwrap() {
-      diff_chars += 1;
-    }
-  }
-
-  diff_chars
-}
-
-fn get_ids_with_one_letter_different(ids: &[String]) -> Vec<String> {
-  let mut new_ids = vec![];
-
-  for (id_idx, id) in ids.iter().enumerate() {
-    for another_id in &ids[id_idx + 1..] {
-      if get_diff_chars_in_str(&id, &another_id) == 1 {
-        new_ids.push(id.clone());
-        new_ids.push(another_id.clone());
-      }
-    }
-  }
-
-  new_ids
-}
-
-fn get_common_letters_of_str(a: &str, b: &str) -> String {
-  let mut new_str = String::new();
-
-  for (idx, a_char) in a.chars().enumerate() {
-    let mut b_chars = b.chars();
-
-    if a_char == b_chars.nth(idx).unwrap() {
-      new_str.push(a_char);
-    }
-  }
-
-  new_str
-}
-
 fn main() {
   let ids = get_ids();
   let checksum = get_checksum_of_ids(&ids);
-  let matching_ids = get_ids_with_one_letter_different(&ids);
+  let matching_ids = find_near_duplicates(&ids, Distance::Hamming(1));
+  let similar_ids = find_near_duplicates(&ids, Distance::Levenshtein(2));
 
-  if matching_ids.iter().count() != 2 {
+  if matching_ids.len() != 1 {
     panic!("Unexpected ids number");
   }
 
-  let common_letters = get_common_letters_of_str(&matching_ids[0], &matching_ids[1]);
-
   println!("Results:");
   println!("- (1) checksum: {}", checksum);
-  println!("- (2) same letters: {}", common_letters);
+  println!("- (2) same letters: {}", matching_ids[0].common_letters);
+  println!("Pairs of ids within 2 edits: {}", similar_ids.len());
 }
 
 #[cfg(test)]
@@ -203,7 +163,7 @@ mod tests {
 
   #[test]
   fn test_get_checksum_of_ids() {
-    let ids = vec![
+    let ids = [
       "abcdef", "bababc", "abbcde", "abcccd", "aabcdd", "abcdee", "ababab",
     ]
     .iter()
@@ -213,23 +173,4 @@ mod tests {
 
     assert_eq!(12, checksum);
   }
-
-  #[test]
-  fn test_get_ids_with_one_letter_different() {
-    let ids = vec![
-      "abcde", "fghij", "klmno", "pqrst", "fguij", "axcye", "wvxyz",
-    ]
-    .iter()
-    .map(|x| x.to_string())
-    .collect::<Vec<String>>();
-    let new_ids = get_ids_with_one_letter_different(&ids);
-
-    assert_eq!(2, new_ids.iter().count());
-  }
-
-  #[test]
-  fn test_get_common_letters_of_str() {
-    let result = get_common_letters_of_str(&"foo".to_string(), &"fio".to_string());
-    assert_eq!("fo", result);
-  }
 }
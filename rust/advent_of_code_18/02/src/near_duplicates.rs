@@ -0,0 +1,284 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Distance {
+  // only substitutions, so the ids must have the same length
+  Hamming(usize),
+  // substitutions, insertions and deletions
+  Levenshtein(usize),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NearDuplicate {
+  // the indexes of the ids, with the lowest first
+  pub first: usize,
+  pub second: usize,
+  pub distance: usize,
+  pub common_letters: String,
+}
+
+// the letters in the same position of both ids
+fn get_hamming_alignment(a: &[char], b: &[char]) -> Option<(usize, String)> {
+  if a.len() != b.len() {
+    return None;
+  }
+
+  let common_letters: String = a
+    .iter()
+    .zip(b.iter())
+    .filter(|(a_char, b_char)| a_char == b_char)
+    .map(|(a_char, _)| *a_char)
+    .collect();
+
+  Some((a.len() - common_letters.chars().count(), common_letters))
+}
+
+// the letters that are kept (not substituted or deleted) when editing one id into the other
+fn get_levenshtein_alignment(a: &[char], b: &[char]) -> (usize, String) {
+  let mut distances: Vec<Vec<usize>> = vec![vec![0; b.len() + 1]; a.len() + 1];
+
+  for (i, row) in distances.iter_mut().enumerate() {
+    row[0] = i;
+  }
+
+  for (j, distance) in distances[0].iter_mut().enumerate() {
+    *distance = j;
+  }
+
+  for i in 1..=a.len() {
+    for j in 1..=b.len() {
+      let substitution = distances[i - 1][j - 1] + if a[i - 1] == b[j - 1] { 0 } else { 1 };
+
+      distances[i][j] = substitution
+        .min(distances[i - 1][j] + 1)
+        .min(distances[i][j - 1] + 1);
+    }
+  }
+
+  let mut common_letters: Vec<char> = vec![];
+  let (mut i, mut j) = (a.len(), b.len());
+
+  while i > 0 && j > 0 {
+    if a[i - 1] == b[j - 1] && distances[i][j] == distances[i - 1][j - 1] {
+      common_letters.push(a[i - 1]);
+      i -= 1;
+      j -= 1;
+    } else if distances[i][j] == distances[i - 1][j - 1] + 1 {
+      i -= 1;
+      j -= 1;
+    } else if distances[i][j] == distances[i - 1][j] + 1 {
+      i -= 1;
+    } else {
+      j -= 1;
+    }
+  }
+
+  common_letters.reverse();
+
+  (
+    distances[a.len()][b.len()],
+    common_letters.into_iter().collect(),
+  )
+}
+
+// The hashes of the id after removing up to `max_removals` letters. For Hamming the removed
+// letters leave a gap, so only ids of the same length share hashes
+fn get_neighbourhood_hashes(id: &[char], max_removals: usize, keep_gaps: bool) -> HashSet<u64> {
+  let mut hashes: HashSet<u64> = HashSet::new();
+  // each variant with the first position that can still be removed
+  let mut variants: Vec<(Vec<Option<char>>, usize)> =
+    vec![(id.iter().map(|letter| Some(*letter)).collect(), 0)];
+
+  for removals in 0..=max_removals {
+    let mut next_variants = vec![];
+
+    for (variant, first_position) in variants {
+      let mut hasher = DefaultHasher::new();
+
+      variant.hash(&mut hasher);
+      hashes.insert(hasher.finish());
+
+      if removals == max_removals {
+        continue;
+      }
+
+      for position in first_position..variant.len() {
+        let mut next_variant = variant.clone();
+
+        if keep_gaps {
+          next_variant[position] = None;
+          next_variants.push((next_variant, position + 1));
+        } else {
+          next_variant.remove(position);
+          next_variants.push((next_variant, position));
+        }
+      }
+    }
+
+    variants = next_variants;
+  }
+
+  hashes
+}
+
+// Two ids within the distance `k` become the same after removing at most `k` letters of each one,
+// so only the ids sharing one of those hashes are compared
+pub fn find_near_duplicates(ids: &[String], distance: Distance) -> Vec<NearDuplicate> {
+  let (max_distance, keep_gaps) = match distance {
+    Distance::Hamming(k) => (k, true),
+    Distance::Levenshtein(k) => (k, false),
+  };
+  let ids_chars: Vec<Vec<char>> = ids.iter().map(|id| id.chars().collect()).collect();
+  let mut hashed_ids: Vec<(u64, usize)> = vec![];
+
+  for (idx, id) in ids_chars.iter().enumerate() {
+    for hash in get_neighbourhood_hashes(id, max_distance, keep_gaps) {
+      hashed_ids.push((hash, idx));
+    }
+  }
+
+  hashed_ids.sort_unstable();
+
+  let mut candidates: HashSet<(usize, usize)> = HashSet::new();
+
+  for group in hashed_ids.chunk_by(|a, b| a.0 == b.0) {
+    for (group_idx, (_, first)) in group.iter().enumerate() {
+      for (_, second) in group[group_idx + 1..].iter() {
+        candidates.insert((*first, *second));
+      }
+    }
+  }
+
+  let mut near_duplicates: Vec<NearDuplicate> = candidates
+    .into_iter()
+    .filter_map(|(first, second)| {
+      let (a, b) = (&ids_chars[first], &ids_chars[second]);
+      let (distance, common_letters) = match distance {
+        Distance::Hamming(_) => get_hamming_alignment(a, b)?,
+        Distance::Levenshtein(_) => get_levenshtein_alignment(a, b),
+      };
+
+      if distance > max_distance {
+        return None;
+      }
+
+      Some(NearDuplicate {
+        first,
+        second,
+        distance,
+        common_letters,
+      })
+    })
+    .collect();
+
+  near_duplicates.sort_by_key(|near_duplicate| (near_duplicate.first, near_duplicate.second));
+
+  near_duplicates
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn to_strings(strs: &[&str]) -> Vec<String> {
+    strs.iter().map(|x| x.to_string()).collect()
+  }
+
+  fn to_chars(id: &str) -> Vec<char> {
+    id.chars().collect()
+  }
+
+  #[test]
+  fn test_alignments() {
+    assert_eq!(
+      get_hamming_alignment(&to_chars("fghij"), &to_chars("fguij")),
+      Some((1, "fgij".to_string()))
+    );
+    assert_eq!(
+      get_hamming_alignment(&to_chars("abc"), &to_chars("ab")),
+      None
+    );
+    assert_eq!(
+      get_levenshtein_alignment(&to_chars("kitten"), &to_chars("sitting")),
+      (3, "ittn".to_string())
+    );
+    assert_eq!(
+      get_levenshtein_alignment(&to_chars("abcde"), &to_chars("bcdef")),
+      (2, "bcde".to_string())
+    );
+    assert_eq!(
+      get_levenshtein_alignment(&to_chars(""), &to_chars("ab")),
+      (2, "".to_string())
+    );
+  }
+
+  #[test]
+  fn test_find_near_duplicates() {
+    let ids = to_strings(&[
+      "abcde", "fghij", "klmno", "pqrst", "fguij", "axcye", "wvxyz", "bcdef",
+    ]);
+    let hamming = find_near_duplicates(&ids, Distance::Hamming(1));
+
+    assert_eq!(
+      hamming,
+      vec![NearDuplicate {
+        first: 1,
+        second: 4,
+        distance: 1,
+        common_letters: "fgij".to_string(),
+      }]
+    );
+
+    let pairs: Vec<(usize, usize, usize)> = find_near_duplicates(&ids, Distance::Levenshtein(2))
+      .iter()
+      .map(|x| (x.first, x.second, x.distance))
+      .collect();
+
+    assert_eq!(pairs, vec![(0, 5, 2), (0, 7, 2), (1, 4, 1)]);
+  }
+
+  // compares the indexed search with all the pairs, for every id of `abc` with up to 3 letters
+  #[test]
+  fn test_find_near_duplicates_small_ids() {
+    let mut ids: Vec<String> = vec![];
+
+    for len in 1..=3 {
+      for code in 0..3_usize.pow(len) {
+        ids.push(
+          (0..len)
+            .map(|idx| (b'a' + (code / 3_usize.pow(idx) % 3) as u8) as char)
+            .collect(),
+        );
+      }
+    }
+
+    for k in 0..3 {
+      for distance in &[Distance::Hamming(k), Distance::Levenshtein(k)] {
+        let mut expected: Vec<(usize, usize)> = vec![];
+
+        for first in 0..ids.len() {
+          for second in first + 1..ids.len() {
+            let (a, b) = (to_chars(&ids[first]), to_chars(&ids[second]));
+            let pair_distance = match distance {
+              Distance::Hamming(_) => get_hamming_alignment(&a, &b).map(|x| x.0),
+              Distance::Levenshtein(_) => Some(get_levenshtein_alignment(&a, &b).0),
+            };
+
+            if pair_distance.is_some_and(|x| x <= k) {
+              expected.push((first, second));
+            }
+          }
+        }
+
+        let found: Vec<(usize, usize)> = find_near_duplicates(&ids, *distance)
+          .iter()
+          .map(|x| (x.first, x.second))
+          .collect();
+
+        assert_eq!(found, expected);
+      }
+    }
+  }
+}
@@ -0,0 +1,249 @@
+use std::cmp::{max, min};
+use std::collections::BTreeMap;
+
+use crate::Claim;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClaimOverlap {
+  // the index of the other claim
+  pub claim: usize,
+  pub area: usize,
+}
+
+// which claims overlap which, by the indexes of the claims
+#[derive(Debug, Clone, PartialEq)]
+pub struct OverlapGraph {
+  overlaps: Vec<Vec<ClaimOverlap>>,
+}
+
+fn get_right(claim: &Claim) -> usize {
+  claim.left_inches + claim.width
+}
+
+fn get_bottom(claim: &Claim) -> usize {
+  claim.top_inches + claim.height
+}
+
+// the length of the vertical intervals covered by at least two of them
+fn get_length_covered_twice(intervals: &[(usize, usize)]) -> usize {
+  let mut ends: Vec<(usize, isize)> = intervals
+    .iter()
+    .flat_map(|(top, bottom)| vec![(*top, 1), (*bottom, -1)])
+    .collect();
+  let mut length = 0;
+  let mut covering = 0;
+  let mut previous_y = 0;
+
+  // at the same y the interval ends go first, so touching intervals don't overlap
+  ends.sort();
+
+  for (y, change) in ends {
+    if covering >= 2 {
+      length += y - previous_y;
+    }
+
+    covering += change;
+    previous_y = y;
+  }
+
+  length
+}
+
+// Sweeps a vertical line through the edges of the claims, keeping the claims it crosses. Between
+// two edges the overlapped area is the same in each column, so there is no per-square storage
+pub fn get_overlapping_area(claims: &[Claim]) -> usize {
+  let mut edges: BTreeMap<usize, (Vec<usize>, Vec<usize>)> = BTreeMap::new();
+
+  for (idx, claim) in claims.iter().enumerate() {
+    edges.entry(claim.left_inches).or_default().0.push(idx);
+    edges.entry(get_right(claim)).or_default().1.push(idx);
+  }
+
+  let mut crossed: BTreeMap<usize, (usize, usize)> = BTreeMap::new();
+  let mut previous_x = 0;
+  let mut area = 0;
+
+  for (x, (starting, ending)) in edges {
+    if crossed.len() >= 2 {
+      let intervals: Vec<(usize, usize)> = crossed.values().cloned().collect();
+
+      area += (x - previous_x) * get_length_covered_twice(&intervals);
+    }
+
+    // the claims without width start and end at the same edge
+    for idx in starting {
+      crossed.insert(idx, (claims[idx].top_inches, get_bottom(&claims[idx])));
+    }
+
+    for idx in ending {
+      crossed.remove(&idx);
+    }
+
+    previous_x = x;
+  }
+
+  area
+}
+
+impl OverlapGraph {
+  // the claims are visited from left to right, only comparing the ones that share columns
+  pub fn new(claims: &[Claim]) -> OverlapGraph {
+    let mut overlaps: Vec<Vec<ClaimOverlap>> = vec![vec![]; claims.len()];
+    let mut sorted_idxs: Vec<usize> = (0..claims.len()).collect();
+    let mut crossed: Vec<usize> = vec![];
+
+    sorted_idxs.sort_by_key(|idx| claims[*idx].left_inches);
+
+    for idx in sorted_idxs {
+      let claim = &claims[idx];
+
+      crossed.retain(|other_idx| get_right(&claims[*other_idx]) > claim.left_inches);
+
+      for other_idx in crossed.iter() {
+        let other = &claims[*other_idx];
+        let width = min(get_right(claim), get_right(other)) - claim.left_inches;
+        let top = max(claim.top_inches, other.top_inches);
+        let bottom = min(get_bottom(claim), get_bottom(other));
+
+        if bottom > top {
+          let area = width * (bottom - top);
+
+          overlaps[idx].push(ClaimOverlap {
+            claim: *other_idx,
+            area,
+          });
+          overlaps[*other_idx].push(ClaimOverlap { claim: idx, area });
+        }
+      }
+
+      crossed.push(idx);
+    }
+
+    for claim_overlaps in overlaps.iter_mut() {
+      claim_overlaps.sort_by_key(|overlap| overlap.claim);
+    }
+
+    OverlapGraph { overlaps }
+  }
+
+  pub fn get_overlaps(&self, claim: usize) -> &[ClaimOverlap] {
+    &self.overlaps[claim]
+  }
+
+  // every pair of overlapping claims once, with the lowest index first
+  pub fn get_pairs(&self) -> Vec<(usize, usize, usize)> {
+    self
+      .overlaps
+      .iter()
+      .enumerate()
+      .flat_map(|(idx, claim_overlaps)| {
+        claim_overlaps
+          .iter()
+          .filter(move |overlap| overlap.claim > idx)
+          .map(move |overlap| (idx, overlap.claim, overlap.area))
+      })
+      .collect()
+  }
+
+  pub fn get_claims_without_overlap(&self) -> Vec<usize> {
+    (0..self.overlaps.len())
+      .filter(|idx| self.overlaps[*idx].is_empty())
+      .collect()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{build_claim_regex, parse_str_into_claim};
+
+  // the per-square count of the previous implementation
+  fn get_overlapping_area_naive(claims: &[Claim]) -> usize {
+    let mut counts: BTreeMap<(usize, usize), usize> = BTreeMap::new();
+
+    for claim in claims {
+      for x in claim.left_inches..get_right(claim) {
+        for y in claim.top_inches..get_bottom(claim) {
+          *counts.entry((x, y)).or_insert(0) += 1;
+        }
+      }
+    }
+
+    counts.values().filter(|count| **count > 1).count()
+  }
+
+  #[test]
+  fn test_get_length_covered_twice() {
+    assert_eq!(get_length_covered_twice(&[(0, 5), (3, 8)]), 2);
+    assert_eq!(get_length_covered_twice(&[(0, 5), (5, 8)]), 0);
+    assert_eq!(
+      get_length_covered_twice(&[(0, 10), (1, 2), (4, 6), (5, 7)]),
+      4
+    );
+    assert_eq!(get_length_covered_twice(&[(0, 10)]), 0);
+  }
+
+  // every group of three claims at 0 or 1 inches from the edges, with sides of 1 or 2 inches
+  #[test]
+  fn test_get_overlapping_area_small_claims() {
+    let mut shapes: Vec<(usize, usize, usize, usize)> = vec![];
+
+    for left_inches in 0..2 {
+      for top_inches in 0..2 {
+        for width in 1..=2 {
+          for height in 1..=2 {
+            shapes.push((left_inches, top_inches, width, height));
+          }
+        }
+      }
+    }
+
+    for first in shapes.iter() {
+      for second in shapes.iter() {
+        for third in shapes.iter() {
+          let claims: Vec<Claim> = [first, second, third]
+            .iter()
+            .enumerate()
+            .map(|(idx, (left_inches, top_inches, width, height))| Claim {
+              id: (idx + 1).to_string(),
+              left_inches: *left_inches,
+              top_inches: *top_inches,
+              width: *width,
+              height: *height,
+            })
+            .collect();
+
+          assert_eq!(
+            get_overlapping_area(&claims),
+            get_overlapping_area_naive(&claims)
+          );
+        }
+      }
+    }
+  }
+
+  #[test]
+  fn test_overlap_graph() {
+    let mut reg = build_claim_regex();
+    let claims: Vec<Claim> = [
+      "#1 @ 1,3: 4x4",
+      "#2 @ 3,1: 4x4",
+      "#3 @ 5,5: 2x2",
+      "#4 @ 4,0: 1x10",
+    ]
+    .iter()
+    .map(|x| parse_str_into_claim(x, &mut reg).unwrap())
+    .collect();
+    let graph = OverlapGraph::new(&claims);
+
+    assert_eq!(graph.get_pairs(), vec![(0, 1, 4), (0, 3, 4), (1, 3, 4)]);
+    assert_eq!(
+      graph.get_overlaps(3),
+      &[
+        ClaimOverlap { claim: 0, area: 4 },
+        ClaimOverlap { claim: 1, area: 4 },
+      ]
+    );
+    assert_eq!(graph.get_claims_without_overlap(), vec![2]);
+  }
+}
@@ -0,0 +1,292 @@
+use std::fs::File;
+use std::io::prelude::*;
+
+use crate::Claim;
+
+// from the lightest to the darkest overlap, relative to the most overlapped cell
+const OVERLAP_SHADES: [char; 4] = [':', '+', '*', '#'];
+const EMPTY_COLOR: [u8; 3] = [245, 235, 215];
+
+#[derive(Debug, PartialEq)]
+pub struct RenderOptions {
+  pub width: usize,
+  pub height: usize,
+  pub ppm_path: Option<String>,
+}
+
+impl Default for RenderOptions {
+  fn default() -> Self {
+    RenderOptions {
+      width: 100,
+      height: 50,
+      ppm_path: None,
+    }
+  }
+}
+
+impl RenderOptions {
+  // e.g. `--width 1000 --height 1000 --ppm fabric.ppm`
+  pub fn parse_args(args: &[String]) -> Result<RenderOptions, String> {
+    let mut options = RenderOptions::default();
+    let mut args_iter = args.iter();
+
+    while let Some(flag) = args_iter.next() {
+      let value = args_iter
+        .next()
+        .ok_or(format!("Missing value for {}", flag))?;
+
+      if flag == "--ppm" {
+        options.ppm_path = Some(value.to_string());
+        continue;
+      }
+
+      let number = value
+        .parse::<usize>()
+        .map_err(|_| format!("Invalid number for {}: {}", flag, value))?;
+
+      match flag.as_ref() {
+        "--width" => options.width = number,
+        "--height" => options.height = number,
+        _ => return Err(format!("Unknown option: {}", flag)),
+      }
+    }
+
+    if options.width == 0 || options.height == 0 {
+      return Err("The size must be at least 1x1".to_string());
+    }
+
+    Ok(options)
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cell {
+  // the number of claims touching the squares of the cell
+  pub claims_num: usize,
+  // the index of the last claim touching it
+  pub last_claim: Option<usize>,
+}
+
+// Each cell has `scale` x `scale` squares, with the same scale in both axes so the whole fabric
+// fits in `width` x `height` cells. With a scale of 1 the overlaps are the exact ones
+pub fn get_scaled_cells(claims: &[Claim], width: usize, height: usize) -> Vec<Vec<Cell>> {
+  let fabric_width = claims
+    .iter()
+    .map(|claim| claim.left_inches + claim.width)
+    .max()
+    .unwrap_or(0);
+  let fabric_height = claims
+    .iter()
+    .map(|claim| claim.top_inches + claim.height)
+    .max()
+    .unwrap_or(0);
+  let scale = [
+    1,
+    fabric_width.div_ceil(width),
+    fabric_height.div_ceil(height),
+  ]
+  .iter()
+  .cloned()
+  .fold(0, usize::max);
+  let empty_cell = Cell {
+    claims_num: 0,
+    last_claim: None,
+  };
+  let mut cells =
+    vec![vec![empty_cell; fabric_width.div_ceil(scale)]; fabric_height.div_ceil(scale)];
+
+  for (idx, claim) in claims.iter().enumerate() {
+    if claim.width == 0 || claim.height == 0 {
+      continue;
+    }
+
+    let columns = claim.left_inches / scale..=(claim.left_inches + claim.width - 1) / scale;
+
+    for row in
+      cells[claim.top_inches / scale..=(claim.top_inches + claim.height - 1) / scale].iter_mut()
+    {
+      for cell in row[columns.clone()].iter_mut() {
+        cell.claims_num += 1;
+        cell.last_claim = Some(idx);
+      }
+    }
+  }
+
+  cells
+}
+
+fn get_max_claims_num(cells: &[Vec<Cell>]) -> usize {
+  cells
+    .iter()
+    .flat_map(|row| row.iter().map(|cell| cell.claims_num))
+    .max()
+    .unwrap_or(0)
+}
+
+// from 0 for two claims to 1 for the most overlapped cell
+fn get_overlap_intensity(claims_num: usize, max_claims_num: usize) -> f64 {
+  if max_claims_num <= 2 {
+    1.0
+  } else {
+    (claims_num - 2) as f64 / (max_claims_num - 2) as f64
+  }
+}
+
+// the claims are letters (repeated every 26 claims) and the overlaps are shades
+pub fn render_ascii(claims: &[Claim], width: usize, height: usize) -> String {
+  let cells = get_scaled_cells(claims, width, height);
+  let max_claims_num = get_max_claims_num(&cells);
+
+  cells
+    .iter()
+    .map(|row| {
+      row
+        .iter()
+        .map(|cell| match (cell.claims_num, cell.last_claim) {
+          (1, Some(idx)) => (b'a' + (idx % 26) as u8) as char,
+          (0, _) | (_, None) => '.',
+          (claims_num, _) => {
+            let intensity = get_overlap_intensity(claims_num, max_claims_num);
+
+            OVERLAP_SHADES[(intensity * (OVERLAP_SHADES.len() - 1) as f64).round() as usize]
+          }
+        })
+        .collect::<String>()
+    })
+    .collect::<Vec<String>>()
+    .join("\n")
+}
+
+// hues spread with the golden ratio, so consecutive claims have distant colours
+fn get_claim_color(idx: usize) -> [u8; 3] {
+  let hue = (idx as f64 * 0.618_033_988_75).fract() * 6.0;
+  let x = 1.0 - (hue % 2.0 - 1.0).abs();
+  let (red, green, blue) = match hue as usize {
+    0 => (1.0, x, 0.0),
+    1 => (x, 1.0, 0.0),
+    2 => (0.0, 1.0, x),
+    3 => (0.0, x, 1.0),
+    4 => (x, 0.0, 1.0),
+    _ => (1.0, 0.0, x),
+  };
+  let to_byte = |channel: f64| (80.0 + channel * 140.0).round() as u8;
+
+  [to_byte(red), to_byte(green), to_byte(blue)]
+}
+
+// from light red for two claims to dark red for the most overlapped cell
+fn get_overlap_color(claims_num: usize, max_claims_num: usize) -> [u8; 3] {
+  let intensity = get_overlap_intensity(claims_num, max_claims_num);
+  let lerp = |from: f64, to: f64| (from + (to - from) * intensity).round() as u8;
+
+  [lerp(255.0, 120.0), lerp(150.0, 0.0), lerp(150.0, 0.0)]
+}
+
+pub fn render_ppm(claims: &[Claim], width: usize, height: usize) -> Vec<u8> {
+  let cells = get_scaled_cells(claims, width, height);
+  let max_claims_num = get_max_claims_num(&cells);
+  let cells_width = cells.first().map_or(0, |row| row.len());
+  let mut bytes: Vec<u8> = format!("P6\n{} {}\n255\n", cells_width, cells.len()).into_bytes();
+
+  for row in cells.iter() {
+    for cell in row {
+      let color = match (cell.claims_num, cell.last_claim) {
+        (1, Some(idx)) => get_claim_color(idx),
+        (0, _) | (_, None) => EMPTY_COLOR,
+        (claims_num, _) => get_overlap_color(claims_num, max_claims_num),
+      };
+
+      bytes.extend_from_slice(&color);
+    }
+  }
+
+  bytes
+}
+
+pub fn write_ppm_file(claims: &[Claim], options: &RenderOptions, path: &str) {
+  let mut file = File::create(path).expect("Unable to create the image file");
+
+  file
+    .write_all(&render_ppm(claims, options.width, options.height))
+    .expect("Unable to write the image file");
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{build_claim_regex, parse_str_into_claim};
+
+  fn parse_claims(strs: &[&str]) -> Vec<Claim> {
+    let mut reg = build_claim_regex();
+
+    strs
+      .iter()
+      .map(|x| parse_str_into_claim(x, &mut reg).unwrap())
+      .collect()
+  }
+
+  fn get_example_claims() -> Vec<Claim> {
+    parse_claims(&["#1 @ 1,3: 4x4", "#2 @ 3,1: 4x4", "#3 @ 5,5: 2x2"])
+  }
+
+  #[test]
+  fn test_parse_args() {
+    let args: Vec<String> = ["--width", "20", "--ppm", "fabric.ppm"]
+      .iter()
+      .map(|x| x.to_string())
+      .collect();
+
+    assert_eq!(
+      RenderOptions::parse_args(&args),
+      Ok(RenderOptions {
+        width: 20,
+        ppm_path: Some("fabric.ppm".to_string()),
+        ..RenderOptions::default()
+      })
+    );
+    assert!(RenderOptions::parse_args(&["--height".to_string(), "0".to_string()]).is_err());
+    assert!(RenderOptions::parse_args(&["--depth".to_string(), "1".to_string()]).is_err());
+  }
+
+  #[test]
+  fn test_render_ascii() {
+    assert_eq!(
+      render_ascii(&get_example_claims(), 10, 10),
+      [".......", "...bbbb", "...bbbb", ".aa##bb", ".aa##bb", ".aaaacc", ".aaaacc",].join("\n")
+    );
+  }
+
+  #[test]
+  fn test_render_ascii_scaled() {
+    let mut claims = get_example_claims();
+
+    claims.extend(parse_claims(&["#4 @ 4,4: 1x1"]));
+
+    // each cell has 2x2 squares
+    assert_eq!(
+      render_ascii(&claims, 4, 10),
+      [".bbb", "a::b", "a:#:", "aa:c"].join("\n")
+    );
+  }
+
+  #[test]
+  fn test_render_ppm() {
+    let claims = parse_claims(&["#1 @ 0,0: 2x1", "#2 @ 1,0: 1x2"]);
+    let ppm = render_ppm(&claims, 10, 10);
+    let header = "P6\n2 2\n255\n";
+    let mut pixels: Vec<u8> = vec![];
+
+    for color in &[
+      get_claim_color(0),
+      get_overlap_color(2, 2),
+      EMPTY_COLOR,
+      get_claim_color(1),
+    ] {
+      pixels.extend_from_slice(color);
+    }
+
+    assert_eq!(&ppm[..header.len()], header.as_bytes());
+    assert_eq!(&ppm[header.len()..], &pixels[..]);
+    assert_ne!(get_claim_color(0), get_claim_color(1));
+  }
+}
@@ -71,8 +71,13 @@ What is the ID of the only claim that doesn't overlap?
 
 */
 
+mod claims_overlap;
+mod fabric_render;
+
+use claims_overlap::{get_overlapping_area, OverlapGraph};
+use fabric_render::RenderOptions;
 use regex::Regex;
-use std::collections::HashMap;
+use std::env;
 use std::fs::File;
 use std::io::prelude::*;
 
@@ -114,123 +119,73 @@ fn get_claims() -> Vec<Claim> {
 
   let claims: Vec<Claim> = contents
     .lines()
-    .map(|x| parse_str_into_claim(&x, &mut reg).unwrap())
+    .map(|x| parse_str_into_claim(x, &mut reg).unwrap())
     .collect();
 
   claims
 }
 
-type CoveredSquares = HashMap<(usize, usize), usize>;
-
-fn get_covered_squares(claims: &[Claim]) -> CoveredSquares {
-  let mut max_x = 0;
-  let mut max_y = 0;
-
-  for claim in claims {
-    let new_x = claim.left_inches + claim.width;
-    let new_y = claim.top_inches + claim.height;
-
-    if new_x > max_x {
-      max_x = new_x;
-    }
-
-    if new_y > max_y {
-      max_y = new_y;
-    }
-  }
-
-  let mut covered_squares = HashMap::with_capacity(max_x * max_y);
-
-  for claim in claims {
-    for x in claim.left_inches..(claim.left_inches + claim.width) {
-      for y in claim.top_inches..(claim.top_inches + claim.height) {
-        let counter = covered_squares.entry((x, y)).or_insert(0);
-        *counter += 1;
-      }
-    }
-  }
-
-  covered_squares
-}
-
-fn get_overlapping_claims_squares_count(
-  claims: &[Claim],
-  covered_squares: Option<&mut CoveredSquares>,
-) -> usize {
-  let mut r: CoveredSquares = match covered_squares {
-    Some(_) => HashMap::new(),
-    None => get_covered_squares(&claims),
-  };
-  let covered_squares = covered_squares.unwrap_or(&mut r);
-  let mut num = 0;
-
-  for &mut value in covered_squares.values_mut() {
-    if value > 1 {
-      num += 1;
-    }
-  }
-
-  num
-}
-
-fn get_claims_without_overlap(
-  claims: &[Claim],
-  covered_squares: Option<&mut CoveredSquares>,
-) -> Vec<String> {
-  let mut r: CoveredSquares = match covered_squares {
-    Some(_) => HashMap::new(),
-    None => get_covered_squares(&claims),
-  };
-  let covered_squares = covered_squares.unwrap_or(&mut r);
-  let mut found_claims = vec![];
-
-  for claim in claims {
-    let mut is_valid = true;
-
-    for x in claim.left_inches..(claim.left_inches + claim.width) {
-      for y in claim.top_inches..(claim.top_inches + claim.height) {
-        let point = (x, y);
-        let counter = covered_squares.get(&point).unwrap();
-
-        if *counter != 1 {
-          is_valid = false;
-          break;
-        }
-      }
-
-      if is_valid != true {
-        break;
-      }
-    }
-
-    if is_valid {
-      found_claims.push(claim.id.clone());
-      break;
-    }
-  }
-
-  found_claims
+// the ids of the claims in the input order
+fn get_claims_without_overlap(claims: &[Claim], graph: &OverlapGraph) -> Vec<String> {
+  graph
+    .get_claims_without_overlap()
+    .into_iter()
+    .map(|idx| claims[idx].id.clone())
+    .collect()
 }
 
 fn main() {
   let claims = get_claims();
+  let args: Vec<String> = env::args().skip(1).collect();
+
+  // `cargo run -- render [--width N] [--height N] [--ppm FILE]`
+  if args.first().map(|x| x.as_ref()) == Some("render") {
+    let options = RenderOptions::parse_args(&args[1..]).unwrap_or_else(|err| {
+      eprintln!("{}", err);
+      std::process::exit(1);
+    });
+
+    match options.ppm_path {
+      Some(ref path) => fabric_render::write_ppm_file(&claims, &options, path),
+      None => println!(
+        "{}",
+        fabric_render::render_ascii(&claims, options.width, options.height)
+      ),
+    }
 
-  let mut covered_squares = get_covered_squares(&claims);
+    return;
+  }
 
-  let overlapping_squares =
-    get_overlapping_claims_squares_count(&claims, Some(&mut covered_squares));
-  let claims_without_overlap = get_claims_without_overlap(&claims, Some(&mut covered_squares));
+  let graph = OverlapGraph::new(&claims);
 
-  if claims_without_overlap.iter().count() != 1 {
-    panic!("Unexpected num of claims withtou overlap");
-  }
+  let overlapping_squares = get_overlapping_area(&claims);
+  let claims_without_overlap = get_claims_without_overlap(&claims, &graph);
+  let overlapping_pairs = graph.get_pairs();
+  let largest_overlap = overlapping_pairs.iter().max_by_key(|(_, _, area)| *area);
+  let most_overlapped_claim = (0..claims.len()).max_by_key(|idx| graph.get_overlaps(*idx).len());
 
   println!("Results:");
   println!("- (1) overlapping squares: {}", overlapping_squares);
   println!(
-    "- (2) claim id without overlap: {:?}",
-    claims_without_overlap[0]
+    "- (2) claim ids without overlap: {:?}",
+    claims_without_overlap
   );
+  println!("Overlapping pairs of claims: {}", overlapping_pairs.len());
+
+  if let Some((first, second, area)) = largest_overlap {
+    println!(
+      "Largest overlap: #{} and #{} share {} squares",
+      claims[*first].id, claims[*second].id, area
+    );
+  }
+
+  if let Some(idx) = most_overlapped_claim {
+    println!(
+      "Most overlapped claim: #{} overlaps {} claims",
+      claims[idx].id,
+      graph.get_overlaps(idx).len()
+    );
+  }
 }
 
 #[cfg(test)]
@@ -238,7 +193,7 @@ mod tests {
   use super::*;
 
   fn parse_str_into_claim_with_regex(s: &str) -> Result<Claim, String> {
-    parse_str_into_claim(&s.to_string(), &mut build_claim_regex())
+    parse_str_into_claim(s, &mut build_claim_regex())
   }
 
   #[test]
@@ -258,7 +213,7 @@ mod tests {
 
   #[test]
   fn test_get_overlapping_claims_squares_count_1() {
-    let claims = vec![
+    let claims = [
       Claim {
         id: "1".to_string(),
         left_inches: 0,
@@ -275,12 +230,12 @@ mod tests {
       },
     ];
 
-    assert_eq!(get_overlapping_claims_squares_count(&claims, None), 1);
+    assert_eq!(get_overlapping_area(&claims), 1);
   }
 
   #[test]
   fn test_get_overlapping_claims_squares_count_2() {
-    let claims = vec![
+    let claims = [
       Claim {
         id: "1".to_string(),
         left_inches: 0,
@@ -297,27 +252,40 @@ mod tests {
       },
     ];
 
-    assert_eq!(get_overlapping_claims_squares_count(&claims, None), 2);
+    assert_eq!(get_overlapping_area(&claims), 2);
   }
 
   #[test]
   fn test_get_overlapping_claims_squares_count_3() {
-    let claims = vec![
+    let claims = [
       parse_str_into_claim_with_regex("#1 @ 100,50: 1x100").unwrap(),
       parse_str_into_claim_with_regex("#2 @ 100,50: 1x100").unwrap(),
     ];
 
-    assert_eq!(get_overlapping_claims_squares_count(&claims, None), 100);
+    assert_eq!(get_overlapping_area(&claims), 100);
   }
 
   #[test]
   fn test_get_overlapping_claims_squares_count_example() {
-    let claims = vec![
+    let claims = [
+      parse_str_into_claim_with_regex("#1 @ 1,3: 4x4").unwrap(),
+      parse_str_into_claim_with_regex("#2 @ 3,1: 4x4").unwrap(),
+      parse_str_into_claim_with_regex("#3 @ 5,5: 2x2").unwrap(),
+    ];
+
+    assert_eq!(get_overlapping_area(&claims), 4);
+  }
+
+  #[test]
+  fn test_get_claims_without_overlap() {
+    let claims = [
       parse_str_into_claim_with_regex("#1 @ 1,3: 4x4").unwrap(),
       parse_str_into_claim_with_regex("#2 @ 3,1: 4x4").unwrap(),
       parse_str_into_claim_with_regex("#3 @ 5,5: 2x2").unwrap(),
+      parse_str_into_claim_with_regex("#4 @ 7,0: 1x1").unwrap(),
     ];
+    let graph = OverlapGraph::new(&claims);
 
-    assert_eq!(get_overlapping_claims_squares_count(&claims, None), 4);
+    assert_eq!(get_claims_without_overlap(&claims, &graph), vec!["3", "4"]);
   }
 }
@@ -1,12 +1,16 @@
 use regex::Regex;
 use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::fmt;
 
-type Minute = usize;
-type Minutes = usize;
+use shift_timeline::{ObservationWindow, Shift};
+use timestamp::Timestamp;
+
+pub type Minute = usize;
+pub type Minutes = usize;
 type MinuteToSleptMinutes = HashMap<Minute, Minutes>;
-type GuardID = usize;
-type GuardIDToSleptMinutes = HashMap<GuardID, MinuteToSleptMinutes>;
+pub type GuardID = usize;
+pub type GuardIDToSleptMinutes = HashMap<GuardID, MinuteToSleptMinutes>;
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum GuardAction {
@@ -17,16 +21,13 @@ pub enum GuardAction {
 
 #[derive(Debug, PartialEq, Eq)]
 pub struct LogEntry {
-  pub month: usize,
-  pub day: usize,
-  pub hour: usize,
-  pub minute: usize,
+  pub timestamp: Timestamp,
   pub action: GuardAction,
 }
 
 impl LogEntry {
   pub fn parse_string(full_str: &str) -> LogEntry {
-    let main_reg = Regex::new(r"^\[1518-(.+?)-(.+?) ([^ ]+?):(.+?)\] (.*?)$").unwrap();
+    let main_reg = Regex::new(r"^\[(.+?)\] (.*?)$").unwrap();
     let caps = main_reg.captures(full_str).unwrap();
 
     fn get_action(action_str: &str) -> GuardAction {
@@ -42,7 +43,7 @@ impl LogEntry {
         return GuardAction::FallAsleep;
       }
 
-      let begins_caps = begins_shift_reg.captures(&action_str).unwrap();
+      let begins_caps = begins_shift_reg.captures(action_str).unwrap();
       let guard_id = begins_caps
         .get(1)
         .unwrap()
@@ -53,30 +54,35 @@ impl LogEntry {
       GuardAction::BeginShift(guard_id)
     }
 
-    let action = caps.get(5).unwrap().as_str().parse::<String>().unwrap();
+    let action = caps.get(2).unwrap().as_str().parse::<String>().unwrap();
 
     LogEntry {
-      month: caps.get(1).unwrap().as_str().parse::<usize>().unwrap(),
-      day: caps.get(2).unwrap().as_str().parse::<usize>().unwrap(),
-      minute: caps.get(4).unwrap().as_str().parse::<usize>().unwrap(),
-      hour: caps.get(3).unwrap().as_str().parse::<usize>().unwrap(),
+      timestamp: Timestamp::parse_string(caps.get(1).unwrap().as_str()).unwrap(),
       action: get_action(&action),
     }
   }
 }
 
+impl fmt::Display for GuardAction {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      GuardAction::WakeUp => write!(f, "wakes up"),
+      GuardAction::FallAsleep => write!(f, "falls asleep"),
+      GuardAction::BeginShift(guard_id) => write!(f, "Guard #{} begins shift", guard_id),
+    }
+  }
+}
+
+// the same format of the input lines
+impl fmt::Display for LogEntry {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "[{}] {}", self.timestamp, self.action)
+  }
+}
+
 impl Ord for LogEntry {
   fn cmp(&self, other: &LogEntry) -> Ordering {
-    match self.month.cmp(&other.month) {
-      Ordering::Equal => match self.day.cmp(&other.day) {
-        Ordering::Equal => match self.hour.cmp(&other.hour) {
-          Ordering::Equal => self.minute.cmp(&other.minute),
-          v => v,
-        },
-        v => v,
-      },
-      v => v,
-    }
+    self.timestamp.cmp(&other.timestamp)
   }
 }
 
@@ -86,60 +92,27 @@ impl PartialOrd for LogEntry {
   }
 }
 
-pub fn build_guard_id_to_slept_minutes_map(entries: &[LogEntry]) -> GuardIDToSleptMinutes {
+// the minutes are the ones of the day, and only the ones inside the window are counted
+pub fn build_guard_id_to_slept_minutes_map(
+  shifts: &[Shift],
+  window: &ObservationWindow,
+) -> GuardIDToSleptMinutes {
   let mut result: GuardIDToSleptMinutes = HashMap::new();
 
-  let mut current_guard_id: Option<usize> = None;
-  let mut current_time: Option<(usize, usize)> = None;
-  let mut current_day: Option<(usize, usize)> = None;
+  for shift in shifts {
+    let guard_map = result.entry(shift.guard_id).or_default();
 
-  for entry in entries {
-    if current_day != Some((entry.month, entry.day)) {
-      current_day = None;
-      current_time = None;
-    }
-
-    match entry.action {
-      GuardAction::BeginShift(guard_id) => {
-        if result.get(&guard_id).is_none() {
-          let mut empty = HashMap::new() as MinuteToSleptMinutes;
+    for sleep in shift.sleeps.iter() {
+      let mut time = sleep.start;
 
-          result.insert(guard_id, empty);
-        };
+      while time < sleep.end {
+        let minute = time.get_minute_of_day();
 
-        current_guard_id = Some(guard_id);
-      }
-      GuardAction::FallAsleep => {
-        current_time = match entry.hour {
-          0 => Some((entry.hour, entry.minute)),
-          _ => Some((0, 0)),
-        };
-        current_day = Some((entry.month, entry.day));
-      }
-      GuardAction::WakeUp => {
-        if current_day.is_none() || current_time.is_none() || current_guard_id.is_none() {
-          panic!(
-            "Unexpected state: {}, {}, {}",
-            current_day.is_none(),
-            current_time.is_none(),
-            current_guard_id.is_none()
-          );
+        if window.contains(minute) {
+          *guard_map.entry(minute).or_insert(0) += 1;
         }
 
-        if let 0 = entry.hour {
-          let current_minute = current_time.unwrap().1;
-          let guard_id_val = current_guard_id.unwrap();
-          let mut guard_map = result.get_mut(&guard_id_val).unwrap();
-
-          for minute in current_minute..entry.minute {
-            let existing_minute = match guard_map.get(&minute) {
-              Some(val) => val + 1,
-              None => 1,
-            };
-
-            guard_map.insert(minute, existing_minute);
-          }
-        }
+        time = time.add_minutes(1);
       }
     }
   }
@@ -196,6 +169,7 @@ pub fn get_guard_with_most_sleep_on_same_minute(
 #[cfg(test)]
 mod tests {
   use super::*;
+  use shift_timeline::build_timeline;
 
   fn get_example_data() -> Vec<LogEntry> {
     vec![
@@ -218,39 +192,59 @@ mod tests {
       "[1518-11-05 00:55] wakes up",
     ]
     .iter()
-    .map(|x| LogEntry::parse_string(&x.to_string()))
+    .map(|x| LogEntry::parse_string(x))
     .collect()
   }
 
+  #[test]
+  fn test_log_entry_display() {
+    for line in &[
+      "[1518-04-22 00:52] wakes up",
+      "[1518-03-11 23:56] Guard #547 begins shift",
+      "[1518-06-08 00:39] falls asleep",
+    ] {
+      assert_eq!(LogEntry::parse_string(line).to_string(), *line);
+    }
+  }
+
   #[test]
   fn test_log_entry_parse_string() {
     assert_eq!(
-      LogEntry::parse_string(&"[1518-04-22 00:52] wakes up".to_string()),
+      LogEntry::parse_string("[1518-04-22 00:52] wakes up"),
       LogEntry {
-        month: 4,
-        day: 22,
-        hour: 0,
-        minute: 52,
+        timestamp: Timestamp {
+          year: 1518,
+          month: 4,
+          day: 22,
+          hour: 0,
+          minute: 52,
+        },
         action: GuardAction::WakeUp
       }
     );
     assert_eq!(
-      LogEntry::parse_string(&"[1518-03-11 23:56] Guard #547 begins shift".to_string()),
+      LogEntry::parse_string("[1518-03-11 23:56] Guard #547 begins shift"),
       LogEntry {
-        month: 3,
-        day: 11,
-        hour: 23,
-        minute: 56,
+        timestamp: Timestamp {
+          year: 1518,
+          month: 3,
+          day: 11,
+          hour: 23,
+          minute: 56,
+        },
         action: GuardAction::BeginShift(547)
       }
     );
     assert_eq!(
-      LogEntry::parse_string(&"[1518-06-08 00:39] falls asleep".to_string()),
+      LogEntry::parse_string("[1518-06-08 00:39] falls asleep"),
       LogEntry {
-        month: 6,
-        day: 8,
-        hour: 0,
-        minute: 39,
+        timestamp: Timestamp {
+          year: 1518,
+          month: 6,
+          day: 8,
+          hour: 0,
+          minute: 39,
+        },
         action: GuardAction::FallAsleep
       }
     );
@@ -258,18 +252,18 @@ mod tests {
 
   #[test]
   fn test_log_entry_sort() {
-    let mut res = vec![
-      LogEntry::parse_string(&"[1518-01-15 23:02] Guard #547 begins shift".to_string()),
-      LogEntry::parse_string(&"[1518-01-10 23:01] Guard #547 begins shift".to_string()),
-      LogEntry::parse_string(&"[1518-04-30 23:04] Guard #547 begins shift".to_string()),
-      LogEntry::parse_string(&"[1518-02-01 23:03] Guard #547 begins shift".to_string()),
-      LogEntry::parse_string(&"[1518-04-30 00:05] Guard #547 begins shift".to_string()),
-      LogEntry::parse_string(&"[1518-04-30 23:06] Guard #547 begins shift".to_string()),
+    let mut res = [
+      LogEntry::parse_string("[1518-01-15 23:02] Guard #547 begins shift"),
+      LogEntry::parse_string("[1518-01-10 23:01] Guard #547 begins shift"),
+      LogEntry::parse_string("[1518-04-30 23:04] Guard #547 begins shift"),
+      LogEntry::parse_string("[1518-02-01 23:03] Guard #547 begins shift"),
+      LogEntry::parse_string("[1518-04-30 00:05] Guard #547 begins shift"),
+      LogEntry::parse_string("[1518-04-30 23:06] Guard #547 begins shift"),
     ];
 
     res.sort();
 
-    let minutes: Vec<usize> = res.iter().map(|x| x.minute).collect();
+    let minutes: Vec<usize> = res.iter().map(|x| x.timestamp.minute).collect();
 
     assert_eq!(minutes, vec![1, 2, 3, 5, 4, 6]);
   }
@@ -277,7 +271,10 @@ mod tests {
   #[test]
   fn test_flow_1() {
     let res = get_example_data();
-    let guard_id_to_slept_minutes_map = build_guard_id_to_slept_minutes_map(&res);
+    let guard_id_to_slept_minutes_map = build_guard_id_to_slept_minutes_map(
+      &build_timeline(&res).unwrap(),
+      &ObservationWindow::default(),
+    );
     let guard_id_with_most_sleeping_minutes =
       get_guard_id_with_most_sleeping_minutes(&guard_id_to_slept_minutes_map);
 
@@ -293,10 +290,44 @@ mod tests {
   #[test]
   fn test_get_guard_with_most_sleep_on_same_minute_example_2() {
     let res = get_example_data();
-    let guard_id_to_slept_minutes_map = build_guard_id_to_slept_minutes_map(&res);
+    let guard_id_to_slept_minutes_map = build_guard_id_to_slept_minutes_map(
+      &build_timeline(&res).unwrap(),
+      &ObservationWindow::default(),
+    );
     let result = get_guard_with_most_sleep_on_same_minute(&guard_id_to_slept_minutes_map);
 
     assert_eq!(result, (99, 45, 3));
     assert_eq!(result.0 * result.1, 4455);
   }
+
+  #[test]
+  fn test_build_guard_id_to_slept_minutes_map_with_window() {
+    let entries: Vec<LogEntry> = [
+      "[1518-11-01 23:50] Guard #10 begins shift",
+      "[1518-11-01 23:58] falls asleep",
+      "[1518-11-02 01:02] wakes up",
+    ]
+    .iter()
+    .map(|x| LogEntry::parse_string(x))
+    .collect();
+    let shifts = build_timeline(&entries).unwrap();
+    let count_minutes = |window: &ObservationWindow| -> Vec<(Minute, Minutes)> {
+      let map = build_guard_id_to_slept_minutes_map(&shifts, window);
+      let mut minutes: Vec<(Minute, Minutes)> = map[&10].iter().map(|(k, v)| (*k, *v)).collect();
+
+      minutes.sort();
+
+      minutes
+    };
+
+    assert_eq!(count_minutes(&ObservationWindow::default()).len(), 60);
+    assert_eq!(
+      count_minutes(&ObservationWindow::parse_string("23:59-00:01").unwrap()),
+      vec![(0, 1), (1439, 1)]
+    );
+    assert_eq!(
+      count_minutes(&ObservationWindow::parse_string("01:00-02:00").unwrap()),
+      vec![(60, 1), (61, 1)]
+    );
+  }
 }
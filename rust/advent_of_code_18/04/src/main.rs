@@ -93,14 +93,21 @@ What is the ID of the guard you chose multiplied by the minute you chose? (In th
 extern crate regex;
 
 mod log_entry;
+mod report;
+mod shift_timeline;
+mod timestamp;
 
+use std::env;
 use std::fs::File;
 use std::io::prelude::*;
 
 use log_entry::{
   build_guard_id_to_slept_minutes_map, get_guard_id_with_most_sleeping_minutes,
-  get_guard_with_most_sleep_on_same_minute, get_most_slept_minute_for_guard, LogEntry,
+  get_guard_with_most_sleep_on_same_minute, get_most_slept_minute_for_guard, GuardIDToSleptMinutes,
+  LogEntry,
 };
+use report::{render_guards_heatmap, render_minute_chart};
+use shift_timeline::{build_timeline, ObservationWindow, Shift};
 
 fn get_log_entries() -> Vec<LogEntry> {
   let mut file = File::open("src/input.txt").expect("Unable to open the file");
@@ -109,19 +116,46 @@ fn get_log_entries() -> Vec<LogEntry> {
     .read_to_string(&mut contents)
     .expect("Unable to read the file");
 
-  let mut entries: Vec<LogEntry> = contents
-    .lines()
-    .map(|x| LogEntry::parse_string(&x.to_string()))
-    .collect();
+  let mut entries: Vec<LogEntry> = contents.lines().map(LogEntry::parse_string).collect();
 
   entries.sort();
 
   entries
 }
 
+fn write_report_file(shifts: &[Shift], map: &GuardIDToSleptMinutes, window: &ObservationWindow) {
+  let mut file = File::create("report.txt").expect("Unable to create the file");
+
+  file
+    .write_all(
+      format!(
+        "{}\n\n{}\n",
+        render_minute_chart(shifts, window),
+        render_guards_heatmap(map, window)
+      )
+      .as_bytes(),
+    )
+    .expect("Unable to write the file");
+}
+
+// the observation window can be passed as `HH:MM-HH:MM`, and it defaults to the midnight hour
+// the first argument other than `report`, e.g. `23:00-01:00`
+fn get_observation_window(args: &[String]) -> ObservationWindow {
+  match args.iter().find(|x| *x != "report") {
+    Some(window_str) => ObservationWindow::parse_string(window_str).unwrap_or_else(|err| {
+      eprintln!("{}", err);
+      std::process::exit(1);
+    }),
+    None => ObservationWindow::default(),
+  }
+}
+
 fn main() {
+  let args: Vec<String> = env::args().skip(1).collect();
+  let window = get_observation_window(&args);
   let entries = get_log_entries();
-  let guard_id_to_slept_minutes_map = build_guard_id_to_slept_minutes_map(&entries);
+  let shifts = build_timeline(&entries).unwrap_or_else(|err| panic!("Invalid log: {}", err));
+  let guard_id_to_slept_minutes_map = build_guard_id_to_slept_minutes_map(&shifts, &window);
   let guard_id_with_most_sleeping_minutes =
     get_guard_id_with_most_sleeping_minutes(&guard_id_to_slept_minutes_map);
 
@@ -145,4 +179,10 @@ fn main() {
     minute_with_most_sleep,
     guard_id_with_most_sleep * minute_with_most_sleep
   );
+
+  // `cargo run -- [HH:MM-HH:MM] report`
+  if args.iter().any(|x| x == "report") {
+    write_report_file(&shifts, &guard_id_to_slept_minutes_map, &window);
+    println!("- wrote the report in report.txt");
+  }
 }
@@ -0,0 +1,213 @@
+use std::collections::BTreeMap;
+
+use log_entry::{GuardID, GuardIDToSleptMinutes, Minute};
+use shift_timeline::{ObservationWindow, Shift};
+use timestamp::MINUTES_IN_HOUR;
+
+// from the least to the most slept minute of each guard
+const HEATMAP_SHADES: [char; 6] = [' ', '.', ':', '+', '*', '#'];
+
+// the minutes of the hour of each column of the window
+fn get_minutes_header(indentation: usize, minutes: &[Minute]) -> Vec<String> {
+  let tens: String = minutes
+    .iter()
+    .map(|x| (b'0' + (x % MINUTES_IN_HOUR / 10) as u8) as char)
+    .collect();
+  let units: String = minutes
+    .iter()
+    .map(|x| (b'0' + (x % 10) as u8) as char)
+    .collect();
+  let padding = " ".repeat(indentation);
+
+  vec![
+    format!("{}{}", padding, tens),
+    format!("{}{}", padding, units),
+  ]
+}
+
+// the chart of the puzzle description, with one row per shift and the date of its window
+pub fn render_minute_chart(shifts: &[Shift], window: &ObservationWindow) -> String {
+  let id_width = shifts
+    .iter()
+    .map(|shift| shift.guard_id.to_string().len() + 1)
+    .max()
+    .unwrap_or(2);
+  let mut lines: Vec<String> = vec![format!("Date   {:<width$}  Minute", "ID", width = id_width)];
+
+  lines.extend(get_minutes_header(7 + id_width + 2, &window.get_minutes()));
+
+  for shift in shifts {
+    let window_start = window.get_start_for_shift(shift);
+    let minutes: String = (0..window.length)
+      .map(|offset| {
+        if shift.is_asleep_at(&window_start.add_minutes(offset)) {
+          '#'
+        } else {
+          '.'
+        }
+      })
+      .collect();
+
+    lines.push(format!(
+      "{:02}-{:02}  {:<width$}  {}",
+      window_start.month,
+      window_start.day,
+      format!("#{}", shift.guard_id),
+      minutes,
+      width = id_width
+    ));
+  }
+
+  lines.join("\n")
+}
+
+// each row has the minutes of one guard, shaded relative to the minute it slept the most
+pub fn render_guards_heatmap(map: &GuardIDToSleptMinutes, window: &ObservationWindow) -> String {
+  let window_minutes = window.get_minutes();
+  let guards: BTreeMap<&GuardID, _> = map.iter().collect();
+  let id_width = guards
+    .keys()
+    .map(|guard_id| guard_id.to_string().len() + 1)
+    .max()
+    .unwrap_or(2);
+  let mut lines: Vec<String> = get_minutes_header(id_width + 2, &window_minutes);
+
+  for (guard_id, minutes_map) in guards {
+    let max_count = minutes_map.values().cloned().max().unwrap_or(0);
+    let total: usize = minutes_map.values().sum();
+    let minutes: String = window_minutes
+      .iter()
+      .map(|minute| {
+        let count = minutes_map.get(minute).cloned().unwrap_or(0);
+
+        HEATMAP_SHADES[(count * (HEATMAP_SHADES.len() - 1)).div_ceil(max_count.max(1))]
+      })
+      .collect();
+
+    lines.push(format!(
+      "{:<width$}  {}  total: {}, max: {}",
+      format!("#{}", guard_id),
+      minutes,
+      total,
+      max_count,
+      width = id_width
+    ));
+  }
+
+  lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use log_entry::{build_guard_id_to_slept_minutes_map, LogEntry};
+  use shift_timeline::build_timeline;
+
+  fn get_example_shifts() -> Vec<Shift> {
+    let entries: Vec<LogEntry> = vec![
+      "[1518-11-01 00:00] Guard #10 begins shift",
+      "[1518-11-01 00:05] falls asleep",
+      "[1518-11-01 00:25] wakes up",
+      "[1518-11-01 00:30] falls asleep",
+      "[1518-11-01 00:55] wakes up",
+      "[1518-11-01 23:58] Guard #99 begins shift",
+      "[1518-11-02 00:40] falls asleep",
+      "[1518-11-02 00:50] wakes up",
+      "[1518-11-03 00:05] Guard #10 begins shift",
+      "[1518-11-03 00:24] falls asleep",
+      "[1518-11-03 00:29] wakes up",
+      "[1518-11-04 00:02] Guard #99 begins shift",
+      "[1518-11-04 00:36] falls asleep",
+      "[1518-11-04 00:46] wakes up",
+      "[1518-11-05 00:03] Guard #99 begins shift",
+      "[1518-11-05 00:45] falls asleep",
+      "[1518-11-05 00:55] wakes up",
+    ]
+    .iter()
+    .map(|x| LogEntry::parse_string(x))
+    .collect();
+
+    build_timeline(&entries).unwrap()
+  }
+
+  #[test]
+  fn test_render_minute_chart() {
+    assert_eq!(
+      render_minute_chart(&get_example_shifts(), &ObservationWindow::default()),
+      [
+        "Date   ID   Minute",
+        "            000000000011111111112222222222333333333344444444445555555555",
+        "            012345678901234567890123456789012345678901234567890123456789",
+        "11-01  #10  .....####################.....#########################.....",
+        "11-02  #99  ........................................##########..........",
+        "11-03  #10  ........................#####...............................",
+        "11-04  #99  ....................................##########..............",
+        "11-05  #99  .............................................##########.....",
+      ]
+      .join("\n")
+    );
+  }
+
+  #[test]
+  fn test_render_guards_heatmap() {
+    let window = ObservationWindow::default();
+    let map = build_guard_id_to_slept_minutes_map(&get_example_shifts(), &window);
+    let heatmap = render_guards_heatmap(&map, &window);
+    let lines: Vec<&str> = heatmap.lines().collect();
+
+    let guard_10_minutes = format!(
+      "{}{}#{} {}{}",
+      " ".repeat(5),
+      "+".repeat(19),
+      "+".repeat(4),
+      "+".repeat(25),
+      " ".repeat(5)
+    );
+    let guard_99_minutes = format!(
+      "{}{}{}#{}{}{}",
+      " ".repeat(36),
+      ":".repeat(4),
+      "*".repeat(5),
+      "*".repeat(4),
+      ":".repeat(5),
+      " ".repeat(5)
+    );
+
+    assert_eq!(lines.len(), 4);
+    assert_eq!(
+      lines[2],
+      format!("#10  {}  total: 50, max: 2", guard_10_minutes)
+    );
+    assert_eq!(
+      lines[3],
+      format!("#99  {}  total: 30, max: 3", guard_99_minutes)
+    );
+  }
+
+  #[test]
+  fn test_render_minute_chart_with_window_across_midnight() {
+    let entries: Vec<LogEntry> = [
+      "[1518-12-31 23:50] Guard #10 begins shift",
+      "[1518-12-31 23:55] falls asleep",
+      "[1519-01-01 00:02] wakes up",
+      "[1519-01-01 23:57] Guard #99 begins shift",
+    ]
+    .iter()
+    .map(|x| LogEntry::parse_string(x))
+    .collect();
+    let shifts = build_timeline(&entries).unwrap();
+    let window = ObservationWindow::parse_string("23:54-00:04").unwrap();
+
+    assert_eq!(
+      render_minute_chart(&shifts, &window),
+      [
+        "Date   ID   Minute",
+        "            5555550000",
+        "            4567890123",
+        "12-31  #10  .#######..",
+        "01-01  #99  ..........",
+      ]
+      .join("\n")
+    );
+  }
+}
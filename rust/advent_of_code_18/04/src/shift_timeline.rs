@@ -0,0 +1,331 @@
+use std::fmt;
+
+use log_entry::{GuardAction, GuardID, LogEntry, Minute, Minutes};
+use timestamp::{Timestamp, MINUTES_IN_DAY, MINUTES_IN_HOUR};
+
+// the minutes are in [start, end)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SleepInterval {
+  pub start: Timestamp,
+  pub end: Timestamp,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Shift {
+  pub guard_id: GuardID,
+  pub start: Timestamp,
+  pub sleeps: Vec<SleepInterval>,
+}
+
+impl Shift {
+  pub fn is_asleep_at(&self, time: &Timestamp) -> bool {
+    self
+      .sleeps
+      .iter()
+      .any(|sleep| sleep.start <= *time && *time < sleep.end)
+  }
+}
+
+// The part of each day where the sleep is observed, which can cross midnight. The puzzle only
+// observes the midnight hour
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ObservationWindow {
+  // minute of the day
+  pub start: Minute,
+  pub length: Minutes,
+}
+
+impl Default for ObservationWindow {
+  fn default() -> Self {
+    ObservationWindow {
+      start: 0,
+      length: MINUTES_IN_HOUR,
+    }
+  }
+}
+
+impl ObservationWindow {
+  // the format is `HH:MM-HH:MM`, and the same start and end is the whole day
+  pub fn parse_string(full_str: &str) -> Result<ObservationWindow, String> {
+    let parse_time = |time_str: &str| -> Option<Minute> {
+      let mut parts = time_str.split(':');
+      let hour = parts.next()?.parse::<usize>().ok()?;
+      let minute = parts.next()?.parse::<usize>().ok()?;
+
+      if parts.next().is_some() || hour >= 24 || minute >= MINUTES_IN_HOUR {
+        return None;
+      }
+
+      Some(hour * MINUTES_IN_HOUR + minute)
+    };
+    let invalid = || format!("Invalid observation window: {}", full_str);
+    let mut times = full_str.split('-');
+    let start = times.next().and_then(parse_time).ok_or_else(invalid)?;
+    let end = times.next().and_then(parse_time).ok_or_else(invalid)?;
+
+    if times.next().is_some() {
+      return Err(invalid());
+    }
+
+    let length = match (end + MINUTES_IN_DAY - start) % MINUTES_IN_DAY {
+      0 => MINUTES_IN_DAY,
+      length => length,
+    };
+
+    Ok(ObservationWindow { start, length })
+  }
+
+  pub fn contains(&self, minute_of_day: Minute) -> bool {
+    (minute_of_day + MINUTES_IN_DAY - self.start) % MINUTES_IN_DAY < self.length
+  }
+
+  // the minutes of the day inside the window, in chronological order
+  pub fn get_minutes(&self) -> Vec<Minute> {
+    (0..self.length)
+      .map(|offset| (self.start + offset) % MINUTES_IN_DAY)
+      .collect()
+  }
+
+  // the first window that hasn't ended when the shift begins, which is the one the shift is for
+  pub fn get_start_for_shift(&self, shift: &Shift) -> Timestamp {
+    let mut start = shift
+      .start
+      .get_previous_day()
+      .with_minute_of_day(self.start);
+
+    while start.add_minutes(self.length) <= shift.start {
+      start = start.get_next_day();
+    }
+
+    start
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimelineErrorKind {
+  NoGuardOnDuty,
+  AlreadyAsleep,
+  NotAsleep,
+  ShiftEndsAsleep,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimelineError {
+  pub line: String,
+  pub kind: TimelineErrorKind,
+}
+
+impl fmt::Display for TimelineError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let reason = match self.kind {
+      TimelineErrorKind::NoGuardOnDuty => "no guard has begun a shift before",
+      TimelineErrorKind::AlreadyAsleep => "the guard is already asleep",
+      TimelineErrorKind::NotAsleep => "the guard is not asleep",
+      TimelineErrorKind::ShiftEndsAsleep => "the previous guard is still asleep",
+    };
+
+    write!(f, "{} at \"{}\"", reason, self.line)
+  }
+}
+
+// the sleeps keep their full timestamps, so the shifts can be observed with any window
+pub fn build_timeline(entries: &[LogEntry]) -> Result<Vec<Shift>, TimelineError> {
+  let mut shifts: Vec<Shift> = vec![];
+  let mut asleep_since: Option<Timestamp> = None;
+  let error = |entry: &LogEntry, kind: TimelineErrorKind| TimelineError {
+    line: entry.to_string(),
+    kind,
+  };
+
+  for entry in entries {
+    match entry.action {
+      GuardAction::BeginShift(guard_id) => {
+        if asleep_since.is_some() {
+          return Err(error(entry, TimelineErrorKind::ShiftEndsAsleep));
+        }
+
+        shifts.push(Shift {
+          guard_id,
+          start: entry.timestamp,
+          sleeps: vec![],
+        });
+      }
+      GuardAction::FallAsleep => {
+        if shifts.is_empty() {
+          return Err(error(entry, TimelineErrorKind::NoGuardOnDuty));
+        }
+
+        if asleep_since.is_some() {
+          return Err(error(entry, TimelineErrorKind::AlreadyAsleep));
+        }
+
+        asleep_since = Some(entry.timestamp);
+      }
+      GuardAction::WakeUp => {
+        let shift = match shifts.last_mut() {
+          Some(shift) => shift,
+          None => return Err(error(entry, TimelineErrorKind::NoGuardOnDuty)),
+        };
+        let start = match asleep_since.take() {
+          Some(start) => start,
+          None => return Err(error(entry, TimelineErrorKind::NotAsleep)),
+        };
+
+        shift.sleeps.push(SleepInterval {
+          start,
+          end: entry.timestamp,
+        });
+      }
+    }
+  }
+
+  if let (Some(entry), Some(_)) = (entries.last(), asleep_since) {
+    return Err(error(entry, TimelineErrorKind::ShiftEndsAsleep));
+  }
+
+  Ok(shifts)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn parse_entries(lines: &[&str]) -> Vec<LogEntry> {
+    lines.iter().map(|x| LogEntry::parse_string(x)).collect()
+  }
+
+  fn parse_time(full_str: &str) -> Timestamp {
+    Timestamp::parse_string(full_str).unwrap()
+  }
+
+  #[test]
+  fn test_build_timeline() {
+    let entries = parse_entries(&[
+      "[1518-11-01 00:00] Guard #10 begins shift",
+      "[1518-11-01 00:05] falls asleep",
+      "[1518-11-01 00:25] wakes up",
+      "[1518-12-31 22:50] Guard #99 begins shift",
+      "[1518-12-31 23:40] falls asleep",
+      "[1519-01-01 01:10] wakes up",
+    ]);
+    let shifts = build_timeline(&entries).unwrap();
+
+    assert_eq!(
+      shifts,
+      vec![
+        Shift {
+          guard_id: 10,
+          start: parse_time("1518-11-01 00:00"),
+          sleeps: vec![SleepInterval {
+            start: parse_time("1518-11-01 00:05"),
+            end: parse_time("1518-11-01 00:25"),
+          }],
+        },
+        Shift {
+          guard_id: 99,
+          start: parse_time("1518-12-31 22:50"),
+          sleeps: vec![SleepInterval {
+            start: parse_time("1518-12-31 23:40"),
+            end: parse_time("1519-01-01 01:10"),
+          }],
+        },
+      ]
+    );
+    assert!(shifts[1].is_asleep_at(&parse_time("1518-12-31 23:59")));
+    assert!(shifts[1].is_asleep_at(&parse_time("1519-01-01 01:09")));
+    assert!(!shifts[1].is_asleep_at(&parse_time("1519-01-01 01:10")));
+  }
+
+  #[test]
+  fn test_observation_window() {
+    let midnight_hour = ObservationWindow::default();
+    let late_night = ObservationWindow::parse_string("23:30-01:00").unwrap();
+
+    assert_eq!(
+      ObservationWindow::parse_string("00:00-01:00"),
+      Ok(midnight_hour.clone())
+    );
+    assert_eq!(
+      late_night,
+      ObservationWindow {
+        start: 23 * 60 + 30,
+        length: 90,
+      }
+    );
+    assert_eq!(
+      ObservationWindow::parse_string("06:00-06:00")
+        .unwrap()
+        .length,
+      MINUTES_IN_DAY
+    );
+    assert!(ObservationWindow::parse_string("23:30").is_err());
+    assert!(ObservationWindow::parse_string("24:00-01:00").is_err());
+
+    assert!(late_night.contains(23 * 60 + 30));
+    assert!(late_night.contains(59));
+    assert!(!late_night.contains(60));
+    assert!(!late_night.contains(23 * 60 + 29));
+    assert_eq!(late_night.get_minutes()[29..31].to_vec(), vec![1439, 0]);
+
+    let shifts = build_timeline(&parse_entries(&[
+      "[1518-12-31 23:58] Guard #10 begins shift",
+      "[1519-01-01 00:30] Guard #99 begins shift",
+      "[1519-01-01 01:00] Guard #10 begins shift",
+    ]))
+    .unwrap();
+    let get_starts = |window: &ObservationWindow| -> Vec<String> {
+      shifts
+        .iter()
+        .map(|shift| window.get_start_for_shift(shift).to_string())
+        .collect()
+    };
+
+    assert_eq!(
+      get_starts(&midnight_hour),
+      vec!["1519-01-01 00:00", "1519-01-01 00:00", "1519-01-02 00:00"]
+    );
+    assert_eq!(
+      get_starts(&late_night),
+      vec!["1518-12-31 23:30", "1518-12-31 23:30", "1519-01-01 23:30"]
+    );
+  }
+
+  #[test]
+  fn test_build_timeline_errors() {
+    let cases = vec![
+      (
+        vec!["[1518-11-01 00:05] falls asleep"],
+        "no guard has begun a shift before at \"[1518-11-01 00:05] falls asleep\"",
+      ),
+      (
+        vec![
+          "[1518-11-01 00:00] Guard #10 begins shift",
+          "[1518-11-01 00:25] wakes up",
+        ],
+        "the guard is not asleep at \"[1518-11-01 00:25] wakes up\"",
+      ),
+      (
+        vec![
+          "[1518-11-01 00:00] Guard #10 begins shift",
+          "[1518-11-01 00:05] falls asleep",
+          "[1518-11-01 00:06] falls asleep",
+        ],
+        "the guard is already asleep at \"[1518-11-01 00:06] falls asleep\"",
+      ),
+      (
+        vec![
+          "[1518-11-01 00:00] Guard #10 begins shift",
+          "[1518-11-01 00:05] falls asleep",
+          "[1518-11-01 23:58] Guard #99 begins shift",
+        ],
+        "the previous guard is still asleep at \"[1518-11-01 23:58] Guard #99 begins shift\"",
+      ),
+    ];
+
+    for (lines, message) in cases {
+      let error = build_timeline(&parse_entries(&lines)).unwrap_err();
+
+      assert_eq!(error.to_string(), message);
+    }
+  }
+}
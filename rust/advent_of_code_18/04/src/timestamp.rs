@@ -0,0 +1,199 @@
+use regex::Regex;
+use std::fmt;
+
+pub const MINUTES_IN_HOUR: usize = 60;
+pub const MINUTES_IN_DAY: usize = 24 * MINUTES_IN_HOUR;
+
+const DAYS_IN_MONTHS: [usize; 12] = [31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
+
+// the fields are in order of significance, so the derived ordering is the chronological one
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timestamp {
+  pub year: usize,
+  pub month: usize,
+  pub day: usize,
+  pub hour: usize,
+  pub minute: usize,
+}
+
+fn is_leap_year(year: usize) -> bool {
+  (year.is_multiple_of(4) && !year.is_multiple_of(100)) || year.is_multiple_of(400)
+}
+
+fn get_days_in_month(year: usize, month: usize) -> usize {
+  if month == 2 && is_leap_year(year) {
+    29
+  } else {
+    DAYS_IN_MONTHS[month - 1]
+  }
+}
+
+impl Timestamp {
+  // the format of the log: `1518-11-01 23:58`. It returns `None` for dates that don't exist
+  pub fn parse_string(full_str: &str) -> Option<Timestamp> {
+    let reg = Regex::new(r"^(\d+)-(\d+)-(\d+) (\d+):(\d+)$").unwrap();
+    let caps = reg.captures(full_str)?;
+    let get_number = |idx: usize| caps.get(idx).unwrap().as_str().parse::<usize>().ok();
+    let timestamp = Timestamp {
+      year: get_number(1)?,
+      month: get_number(2)?,
+      day: get_number(3)?,
+      hour: get_number(4)?,
+      minute: get_number(5)?,
+    };
+
+    let is_valid = (1..=12).contains(&timestamp.month)
+      && (1..=get_days_in_month(timestamp.year, timestamp.month)).contains(&timestamp.day)
+      && timestamp.hour < 24
+      && timestamp.minute < MINUTES_IN_HOUR;
+
+    if is_valid {
+      Some(timestamp)
+    } else {
+      None
+    }
+  }
+
+  pub fn get_minute_of_day(&self) -> usize {
+    self.hour * MINUTES_IN_HOUR + self.minute
+  }
+
+  // the same day at the given minute of it
+  pub fn with_minute_of_day(&self, minute_of_day: usize) -> Timestamp {
+    Timestamp {
+      hour: minute_of_day / MINUTES_IN_HOUR,
+      minute: minute_of_day % MINUTES_IN_HOUR,
+      ..*self
+    }
+  }
+
+  pub fn get_next_day(&self) -> Timestamp {
+    let mut next = *self;
+
+    if next.day < get_days_in_month(next.year, next.month) {
+      next.day += 1;
+    } else if next.month < 12 {
+      next.month += 1;
+      next.day = 1;
+    } else {
+      next.year += 1;
+      next.month = 1;
+      next.day = 1;
+    }
+
+    next
+  }
+
+  pub fn get_previous_day(&self) -> Timestamp {
+    let mut previous = *self;
+
+    if previous.day > 1 {
+      previous.day -= 1;
+    } else if previous.month > 1 {
+      previous.month -= 1;
+      previous.day = get_days_in_month(previous.year, previous.month);
+    } else {
+      previous.year -= 1;
+      previous.month = 12;
+      previous.day = 31;
+    }
+
+    previous
+  }
+
+  pub fn add_minutes(&self, minutes: usize) -> Timestamp {
+    let total = self.get_minute_of_day() + minutes;
+    let mut result = self.with_minute_of_day(total % MINUTES_IN_DAY);
+
+    for _ in 0..total / MINUTES_IN_DAY {
+      result = result.get_next_day();
+    }
+
+    result
+  }
+}
+
+impl fmt::Display for Timestamp {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(
+      f,
+      "{:04}-{:02}-{:02} {:02}:{:02}",
+      self.year, self.month, self.day, self.hour, self.minute
+    )
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn parse(full_str: &str) -> Timestamp {
+    Timestamp::parse_string(full_str).unwrap()
+  }
+
+  #[test]
+  fn test_parse_string() {
+    assert_eq!(
+      parse("1518-11-01 23:58"),
+      Timestamp {
+        year: 1518,
+        month: 11,
+        day: 1,
+        hour: 23,
+        minute: 58,
+      }
+    );
+    assert_eq!(parse("1518-03-11 00:05").to_string(), "1518-03-11 00:05");
+
+    for invalid in &[
+      "1518-02-29 00:00",
+      "1518-13-01 00:00",
+      "1518-04-31 00:00",
+      "1518-04-01 24:00",
+      "1518-04-01 00:60",
+      "1518-04-01",
+    ] {
+      assert_eq!(Timestamp::parse_string(invalid), None);
+    }
+
+    assert!(Timestamp::parse_string("1520-02-29 00:00").is_some());
+  }
+
+  #[test]
+  fn test_day_rollover() {
+    let cases = [
+      ("1518-02-28 10:00", "1518-03-01 10:00"),
+      ("1520-02-28 10:00", "1520-02-29 10:00"),
+      ("1518-04-30 10:00", "1518-05-01 10:00"),
+      ("1518-12-31 10:00", "1519-01-01 10:00"),
+    ];
+
+    for (day, next_day) in cases.iter() {
+      assert_eq!(parse(day).get_next_day(), parse(next_day));
+      assert_eq!(parse(next_day).get_previous_day(), parse(day));
+    }
+  }
+
+  #[test]
+  fn test_add_minutes() {
+    assert_eq!(
+      parse("1518-12-31 23:58").add_minutes(5),
+      parse("1519-01-01 00:03")
+    );
+    assert_eq!(
+      parse("1518-11-01 00:05").add_minutes(0),
+      parse("1518-11-01 00:05")
+    );
+    assert_eq!(
+      parse("1518-11-01 00:05").add_minutes(2 * MINUTES_IN_DAY + 60),
+      parse("1518-11-03 01:05")
+    );
+  }
+
+  #[test]
+  fn test_ordering() {
+    assert!(parse("1518-12-31 23:59") < parse("1519-01-01 00:00"));
+    assert!(parse("1518-11-01 23:58") < parse("1518-11-02 00:00"));
+    assert!(parse("1518-11-01 00:58") < parse("1518-11-01 23:00"));
+  }
+}
@@ -56,79 +56,244 @@ type and fully reacting the result?
 
 */
 
+mod reaction_rules;
+
+use reaction_rules::ReactionRules;
+use std::env;
 use std::fs::File;
+use std::hash::Hash;
+use std::io;
 use std::io::prelude::*;
+use std::str;
+use std::thread;
+use std::time::Instant;
 
-type Polymer = Vec<char>;
+type Unit = u8;
+type Polymer = Vec<Unit>;
 
-fn get_input_polymer() -> Polymer {
-  let mut file = File::open("src/input.txt").expect("Unable to open the file");
-  let mut contents = String::new();
-  file
-    .read_to_string(&mut contents)
-    .expect("Unable to read the file");
+fn get_input_polymer(rules: &ReactionRules<Unit>) -> Polymer {
+  let file = File::open("src/input.txt").expect("Unable to open the file");
 
-  contents.chars().collect()
+  react_reader(file, rules).expect("Unable to read the file")
 }
 
-// optimized using: https://sts10.github.io/2018/12/07/optimizing-rust-advent-of-code-day-5.html
-// for exercise 1
-fn react(mut polymer: Polymer) -> Polymer {
-  let mut p_vec_len = polymer.len();
-  let mut previous_c: char;
-  let mut index = 1;
+fn react<T, I>(units: I, rules: &ReactionRules<T>) -> Vec<T>
+where
+  T: Copy + Eq + Hash,
+  I: IntoIterator<Item = T>,
+{
+  let mut stack: Vec<T> = vec![];
+
+  for unit in units {
+    rules.push_unit(&mut stack, unit);
+  }
+
+  stack
+}
+
+// The units a polymer can be read as: bytes for the puzzle rules, and characters for rule sets with
+// non-ASCII units
+trait PolymerUnit: Copy + Eq + Hash {
+  // calls `push` with the units of the complete part of `bytes` and returns its length, since a
+  // chunk of the reader can end in the middle of a unit
+  fn decode<F: FnMut(Self)>(bytes: &[u8], push: F) -> io::Result<usize>;
+}
 
-  while index < p_vec_len {
-    previous_c = polymer[index - 1];
+impl PolymerUnit for u8 {
+  fn decode<F: FnMut(Self)>(bytes: &[u8], mut push: F) -> io::Result<usize> {
+    for unit in bytes {
+      if !unit.is_ascii_whitespace() {
+        push(*unit);
+      }
+    }
 
-    if do_these_two_chars_cancel(polymer[index], previous_c) {
-      polymer.drain((index - 1)..=index);
-      p_vec_len -= 2;
+    Ok(bytes.len())
+  }
+}
 
-      if index > 1 {
-        index -= 1
+impl PolymerUnit for char {
+  fn decode<F: FnMut(Self)>(bytes: &[u8], mut push: F) -> io::Result<usize> {
+    let valid_len = match str::from_utf8(bytes) {
+      Ok(text) => text.len(),
+      Err(err) if err.error_len().is_none() => err.valid_up_to(),
+      Err(err) => return Err(io::Error::new(io::ErrorKind::InvalidData, err)),
+    };
+
+    for unit in str::from_utf8(&bytes[..valid_len]).unwrap().chars() {
+      if !unit.is_whitespace() {
+        push(unit);
       }
-    } else {
-      index += 1;
     }
+
+    Ok(valid_len)
+  }
+}
+
+// reads the polymer in chunks, so it doesn't need to be fully in memory before reacting
+fn react_reader<R, T>(mut reader: R, rules: &ReactionRules<T>) -> io::Result<Vec<T>>
+where
+  R: Read,
+  T: PolymerUnit,
+{
+  let mut stack: Vec<T> = vec![];
+  let mut buffer = [0; 8192];
+  let mut pending_bytes: Vec<u8> = vec![];
+
+  loop {
+    let read_bytes = match reader.read(&mut buffer) {
+      Ok(0) => break,
+      Ok(read_bytes) => read_bytes,
+      Err(ref err) if err.kind() == io::ErrorKind::Interrupted => continue,
+      Err(err) => return Err(err),
+    };
+
+    pending_bytes.extend_from_slice(&buffer[..read_bytes]);
+
+    let decoded_len = T::decode(&pending_bytes, |unit| rules.push_unit(&mut stack, unit))?;
+
+    pending_bytes.drain(..decoded_len);
+  }
+
+  if !pending_bytes.is_empty() {
+    return Err(io::Error::new(
+      io::ErrorKind::InvalidData,
+      "The polymer ends in the middle of a unit",
+    ));
   }
 
-  polymer
+  Ok(stack)
 }
 
-fn get_shortest_polymer_length_by_removing_one_type(polymer: &[char]) -> usize {
-  let mut lowest = polymer.len();
+fn get_reacted_length_without_type<T: Copy + Eq + Hash>(
+  polymer: &[T],
+  unit_type: &[T],
+  rules: &ReactionRules<T>,
+) -> usize {
+  react(
+    polymer
+      .iter()
+      .cloned()
+      .filter(|unit| !unit_type.contains(unit)),
+    rules,
+  )
+  .len()
+}
 
-  for letter_num in b'a'..=b'z' {
-    let letter = letter_num as char;
-    let mut new_polymer = polymer.to_owned();
-    let letter_uppercase = letter.to_uppercase().to_string();
+// Removing a type and reacting gives the same result when starting from the reacted polymer (the
+// reactions done before still happen), which is much shorter than the original one. That is not
+// the case with transformations, which can create or consume units of the removed type
+fn check_removal_rules<T: Copy + Eq + Hash>(rules: &ReactionRules<T>) -> Result<(), String> {
+  if rules.has_transformations() {
+    return Err(
+      "The removal from the reacted polymer needs rules without transformations".to_string(),
+    );
+  }
+
+  Ok(())
+}
 
-    new_polymer.retain(|&c| c.to_uppercase().to_string() != letter_uppercase);
+fn get_shortest_polymer_length_by_removing_one_type_sequentially<T: Copy + Eq + Hash>(
+  reacted_polymer: &[T],
+  rules: &ReactionRules<T>,
+) -> Result<usize, String> {
+  check_removal_rules(rules)?;
+
+  Ok(
+    rules
+      .get_unit_types()
+      .iter()
+      .map(|unit_type| get_reacted_length_without_type(reacted_polymer, unit_type, rules))
+      .min()
+      .unwrap_or(reacted_polymer.len()),
+  )
+}
 
-    new_polymer = react(new_polymer);
+fn get_shortest_polymer_length_by_removing_one_type<T: Copy + Eq + Hash + Send + Sync>(
+  reacted_polymer: &[T],
+  rules: &ReactionRules<T>,
+) -> Result<usize, String> {
+  check_removal_rules(rules)?;
 
-    let len = new_polymer.len();
+  let unit_types = rules.get_unit_types();
 
-    if len < lowest {
-      lowest = len;
-    }
+  if unit_types.is_empty() {
+    return Ok(reacted_polymer.len());
   }
 
-  lowest
+  let threads_num = thread::available_parallelism().map_or(1, |num| num.get());
+  let chunk_size = unit_types.len().div_ceil(threads_num);
+
+  Ok(thread::scope(|scope| {
+    let handles: Vec<_> = unit_types
+      .chunks(chunk_size)
+      .map(|chunk| {
+        scope.spawn(move || {
+          chunk
+            .iter()
+            .map(|unit_type| get_reacted_length_without_type(reacted_polymer, unit_type, rules))
+            .min()
+            .unwrap()
+        })
+      })
+      .collect();
+
+    handles
+      .into_iter()
+      .map(|handle| handle.join().unwrap())
+      .min()
+      .unwrap()
+  }))
 }
 
-fn do_these_two_chars_cancel(a: char, b: char) -> bool {
-  a.eq_ignore_ascii_case(&b) && a.is_uppercase() == b.is_lowercase()
+fn print_benchmark(iterations: u32, rules: &ReactionRules<Unit>) {
+  let start = Instant::now();
+  let mut reacted_polymer: Polymer = vec![];
+
+  for _ in 0..iterations {
+    reacted_polymer = get_input_polymer(rules);
+  }
+
+  let reaction_duration = start.elapsed() / iterations;
+
+  let start = Instant::now();
+
+  for _ in 0..iterations {
+    get_shortest_polymer_length_by_removing_one_type_sequentially(&reacted_polymer, rules).unwrap();
+  }
+
+  let sequential_duration = start.elapsed() / iterations;
+
+  let start = Instant::now();
+
+  for _ in 0..iterations {
+    get_shortest_polymer_length_by_removing_one_type(&reacted_polymer, rules).unwrap();
+  }
+
+  let parallel_duration = start.elapsed() / iterations;
+
+  println!("Benchmark (average of {} iterations):", iterations);
+  println!("- reading and reacting the input: {:?}", reaction_duration);
+  println!("- removal trials, sequential: {:?}", sequential_duration);
+  println!("- removal trials, parallel: {:?}", parallel_duration);
 }
 
 fn main() {
-  let start_polymer = get_input_polymer();
-  let result_a = react(start_polymer.clone());
-  let result_b = get_shortest_polymer_length_by_removing_one_type(&start_polymer);
+  let rules = ReactionRules::default();
+  let args: Vec<String> = env::args().skip(1).collect();
+
+  // `cargo run --release -- benchmark`
+  if args.first().map(|x| x.as_ref()) == Some("benchmark") {
+    print_benchmark(20, &rules);
+
+    return;
+  }
+
+  let reacted_polymer = get_input_polymer(&rules);
+  let result_b = get_shortest_polymer_length_by_removing_one_type(&reacted_polymer, &rules)
+    .expect("Unexpected rules for the removal");
 
   println!("Results:");
-  println!("- (1) final polymer length: {}", result_a.len());
+  println!("- (1) final polymer length: {}", reacted_polymer.len());
   println!("- (2) final polymer length: {}", result_b);
 }
 
@@ -136,25 +301,101 @@ fn main() {
 mod tests {
   use super::*;
 
-  use std::iter::FromIterator;
+  fn react_str(polymer: &str) -> String {
+    String::from_utf8(react(polymer.bytes(), &ReactionRules::default())).unwrap()
+  }
 
   #[test]
   fn test_react() {
-    fn test_this(s1: &str, s2: &str) {
-      let result = react(s1.to_string().chars().collect());
+    assert_eq!(react_str("dabAcCaCBAcCcaDA"), "dabCBAcaDA");
+    assert_eq!(react_str("abcC"), "ab");
+    assert_eq!(react_str("aA"), "");
+    assert_eq!(react_str("abBA"), "");
+    assert_eq!(react_str("aabAAB"), "aabAAB");
+  }
 
-      assert_eq!(String::from_iter(result), s2);
-    }
+  #[test]
+  fn test_react_reader() {
+    let reader = io::Cursor::new("dabAcCaCBAcCcaDA\n");
+
+    assert_eq!(
+      react_reader(reader, &ReactionRules::default()).unwrap(),
+      b"dabCBAcaDA".to_vec()
+    );
+  }
 
-    test_this("dabAcCaCBAcCcaDA", "dabCBAcaDA");
-    test_this("abcC", "ab");
+  // the chunks of the reader split the two bytes characters, and an incomplete one is an error
+  #[test]
+  fn test_react_reader_non_ascii() {
+    let rules = ReactionRules::new(&[('α', 'Α')], &[]);
+    let polymer = "αβ".repeat(3000) + "αΑ";
+
+    assert_eq!(
+      react_reader(io::Cursor::new(polymer.as_bytes()), &rules).unwrap(),
+      "αβ".repeat(3000).chars().collect::<Vec<char>>()
+    );
+    assert!(react_reader(io::Cursor::new(&"αβ".as_bytes()[..3]), &rules).is_err());
+    assert!(react_reader(io::Cursor::new(&[b'a', 0xff, b'b']), &rules).is_err());
   }
 
   #[test]
   fn test_get_shortest_polymer_length_by_removing_one_type() {
-    let polymer: Vec<char> = "dabAcCaCBAcCcaDA".chars().collect();
-    let result = get_shortest_polymer_length_by_removing_one_type(&polymer);
+    let rules = ReactionRules::default();
+    let polymer = react("dabAcCaCBAcCcaDA".bytes(), &rules);
+
+    assert_eq!(
+      get_shortest_polymer_length_by_removing_one_type(&polymer, &rules),
+      Ok(4)
+    );
+    assert_eq!(
+      get_shortest_polymer_length_by_removing_one_type_sequentially(&polymer, &rules),
+      Ok(4)
+    );
+  }
 
-    assert_eq!(result, 4);
+  #[test]
+  fn test_removing_from_reacted_polymer() {
+    let polymer = "dabAcCaCBAcCcaDAxXbBcaAdDCeEfgGF";
+    let rules = ReactionRules::default();
+    let polymer: Polymer = polymer.bytes().collect();
+    let reacted_polymer = react(polymer.iter().cloned(), &rules);
+
+    for unit_type in rules.get_unit_types() {
+      assert_eq!(
+        get_reacted_length_without_type(&reacted_polymer, unit_type, &rules),
+        get_reacted_length_without_type(&polymer, unit_type, &rules)
+      );
+    }
+  }
+
+  // `xy` becomes `a` after removing the `A` of the original polymer, but in the reacted one it has
+  // already annihilated with it
+  #[test]
+  fn test_removing_with_transformations() {
+    let rules = ReactionRules::new(&[(b'a', b'A')], &[(b'x', b'y', b'a')]);
+    let polymer: Polymer = b"xyA".to_vec();
+    let reacted_polymer = react(polymer.iter().cloned(), &rules);
+
+    assert_eq!(get_reacted_length_without_type(&polymer, b"aA", &rules), 1);
+    assert_eq!(
+      get_reacted_length_without_type(&reacted_polymer, b"aA", &rules),
+      0
+    );
+    assert!(get_shortest_polymer_length_by_removing_one_type(&reacted_polymer, &rules).is_err());
+    assert!(
+      get_shortest_polymer_length_by_removing_one_type_sequentially(&reacted_polymer, &rules)
+        .is_err()
+    );
+  }
+
+  #[test]
+  fn test_removing_non_ascii_types() {
+    let rules = ReactionRules::new(&[('α', 'Α'), ('β', 'Β')], &[]);
+    let polymer = react("αβΑΑβΒΒα".chars(), &rules);
+
+    assert_eq!(
+      get_shortest_polymer_length_by_removing_one_type(&polymer, &rules),
+      Ok(0)
+    );
   }
 }
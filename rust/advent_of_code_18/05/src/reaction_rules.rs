@@ -0,0 +1,167 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Reaction<T> {
+  Annihilate,
+  Transform(T),
+}
+
+// The reactions of two adjacent units. The annihilations happen in both orders (`aA` and `Aa`) but
+// the transformations only in the given one
+#[derive(Debug, Clone)]
+pub struct ReactionRules<T: Copy + Eq + Hash> {
+  reactions: HashMap<(T, T), Reaction<T>>,
+  // the units that annihilate together, in the order of the annihilations
+  unit_types: Vec<Vec<T>>,
+}
+
+impl<T: Copy + Eq + Hash> ReactionRules<T> {
+  pub fn new(annihilations: &[(T, T)], transformations: &[(T, T, T)]) -> ReactionRules<T> {
+    let mut reactions: HashMap<(T, T), Reaction<T>> = HashMap::new();
+    let mut unit_types: Vec<Vec<T>> = vec![];
+
+    for (a, b) in annihilations {
+      reactions.insert((*a, *b), Reaction::Annihilate);
+      reactions.insert((*b, *a), Reaction::Annihilate);
+
+      let mut unit_type = vec![*a];
+
+      if b != a {
+        unit_type.push(*b);
+      }
+
+      // a unit annihilating with units of two pairs joins their types
+      unit_types.retain(|other_type| {
+        if other_type.contains(a) || other_type.contains(b) {
+          for unit in other_type {
+            if !unit_type.contains(unit) {
+              unit_type.push(*unit);
+            }
+          }
+
+          false
+        } else {
+          true
+        }
+      });
+      unit_types.push(unit_type);
+    }
+
+    for (first, second, result) in transformations {
+      reactions.insert((*first, *second), Reaction::Transform(*result));
+    }
+
+    ReactionRules {
+      reactions,
+      unit_types,
+    }
+  }
+
+  pub fn get_unit_types(&self) -> &[Vec<T>] {
+    &self.unit_types
+  }
+
+  pub fn has_transformations(&self) -> bool {
+    self
+      .reactions
+      .values()
+      .any(|reaction| matches!(reaction, Reaction::Transform(_)))
+  }
+
+  pub fn get_reaction(&self, first: T, second: T) -> Option<Reaction<T>> {
+    self.reactions.get(&(first, second)).cloned()
+  }
+
+  // the stack has the already reacted units, so a new unit can only react with its top. The unit
+  // created by a transformation can react again with the new top
+  pub fn push_unit(&self, stack: &mut Vec<T>, unit: T) {
+    let mut unit = unit;
+
+    loop {
+      match stack.last().and_then(|top| self.get_reaction(*top, unit)) {
+        Some(Reaction::Annihilate) => {
+          stack.pop();
+          return;
+        }
+        Some(Reaction::Transform(result)) => {
+          stack.pop();
+          unit = result;
+        }
+        None => {
+          stack.push(unit);
+          return;
+        }
+      }
+    }
+  }
+}
+
+// the rules of the puzzle: the same type with opposite polarity (case) annihilate
+impl Default for ReactionRules<u8> {
+  fn default() -> Self {
+    let annihilations: Vec<(u8, u8)> = (b'a'..=b'z')
+      .map(|unit| (unit, unit.to_ascii_uppercase()))
+      .collect();
+
+    ReactionRules::new(&annihilations, &[])
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn react_chars(rules: &ReactionRules<char>, polymer: &str) -> String {
+    let mut stack: Vec<char> = vec![];
+
+    for unit in polymer.chars() {
+      rules.push_unit(&mut stack, unit);
+    }
+
+    stack.into_iter().collect()
+  }
+
+  #[test]
+  fn test_default_rules() {
+    let rules = ReactionRules::default();
+
+    assert_eq!(rules.get_reaction(b'a', b'A'), Some(Reaction::Annihilate));
+    assert_eq!(rules.get_reaction(b'Z', b'z'), Some(Reaction::Annihilate));
+    assert_eq!(rules.get_reaction(b'a', b'a'), None);
+    assert_eq!(rules.get_reaction(b'a', b'B'), None);
+    assert_eq!(rules.get_unit_types().len(), 26);
+    assert_eq!(rules.get_unit_types()[2], vec![b'c', b'C']);
+    assert!(!rules.has_transformations());
+  }
+
+  #[test]
+  fn test_non_ascii_rules() {
+    let rules = ReactionRules::new(&[('α', 'Α'), ('β', 'Β')], &[]);
+
+    assert_eq!(react_chars(&rules, "αβΒγΑ"), "αγΑ");
+    assert_eq!(react_chars(&rules, "ΑβΒα"), "");
+  }
+
+  #[test]
+  fn test_transformations() {
+    // x followed by y becomes z, and z annihilates with w
+    let rules = ReactionRules::new(&[('z', 'w')], &[('x', 'y', 'z'), ('z', 'z', 'x')]);
+
+    assert_eq!(react_chars(&rules, "xy"), "z");
+    assert_eq!(react_chars(&rules, "yx"), "yx");
+    assert_eq!(react_chars(&rules, "wxy"), "");
+    assert_eq!(react_chars(&rules, "xyxyy"), "z");
+    assert!(rules.has_transformations());
+  }
+
+  #[test]
+  fn test_unit_types() {
+    let rules = ReactionRules::new(&[('a', 'A'), ('b', 'B'), ('A', 'ä')], &[]);
+
+    assert_eq!(
+      rules.get_unit_types(),
+      &[vec!['b', 'B'], vec!['A', 'ä', 'a']]
+    );
+  }
+}
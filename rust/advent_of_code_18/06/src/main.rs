@@ -113,9 +113,13 @@ coordinates of less than 10000?
 
 extern crate regex;
 
+mod region_map;
+mod safe_region;
+
 use regex::Regex;
-use std::collections::HashMap;
-use std::collections::HashSet;
+use region_map::{Metric, RegionMap};
+use safe_region::get_region_area;
+use std::env;
 use std::fs::File;
 use std::io::prelude::*;
 
@@ -129,20 +133,6 @@ struct Boundary {
   min_y: CoordUnit,
 }
 
-impl Boundary {
-  fn is_point_on_edge(&self, point: &mut Coord) -> bool {
-    if point.x == self.max_x || point.x == self.min_x {
-      return true;
-    }
-
-    if point.y == self.max_y || point.y == self.min_y {
-      return true;
-    }
-
-    false
-  }
-}
-
 #[derive(Debug, PartialEq, Eq)]
 struct Coord {
   x: CoordUnit,
@@ -165,8 +155,9 @@ impl Coord {
     }
   }
 
+  #[cfg(test)]
   fn calc_manhattan_distance(&self, other: &Coord) -> usize {
-    (self.x - other.x).abs() as usize + (self.y - other.y).abs() as usize
+    (self.x - other.x).unsigned_abs() as usize + (self.y - other.y).unsigned_abs() as usize
   }
 }
 
@@ -177,10 +168,10 @@ fn get_input_coords() -> Vec<Coord> {
     .read_to_string(&mut contents)
     .expect("Unable to read the file");
 
-  contents.lines().map(|x| Coord::from_str(&x)).collect()
+  contents.lines().map(Coord::from_str).collect()
 }
 
-fn get_coords_boundary(coords: &mut Vec<Coord>) -> Boundary {
+fn get_coords_boundary(coords: &[Coord]) -> Boundary {
   let mut max_x = coords[0].x;
   let mut min_x = coords[0].x;
   let mut max_y = coords[0].y;
@@ -209,119 +200,47 @@ fn get_coords_boundary(coords: &mut Vec<Coord>) -> Boundary {
   }
 }
 
-fn get_closest_vector_idx_if_one_for_point(
-  coords: &mut Vec<Coord>,
-  point: &mut Coord,
-) -> Option<usize> {
-  let mut current_distance = 0;
-  let mut current_index = None;
-
-  for (idx, coord) in coords.iter().enumerate() {
-    let distance = point.calc_manhattan_distance(coord);
-
-    if idx == 0 || distance < current_distance {
-      current_distance = distance;
-      current_index = Some(idx);
-    } else if distance == current_distance {
-      current_index = None;
-    }
-  }
-
-  current_index
-}
-
-type AreaCount = usize;
-
-fn get_biggest_finite_area(coords: &mut Vec<Coord>) -> Option<usize> {
-  let boundary = get_coords_boundary(coords);
-  type PointIdx = usize;
-  let mut point_idx_to_count: HashMap<PointIdx, AreaCount> = HashMap::new();
-  let mut impossible_index: HashSet<PointIdx> = HashSet::new();
-
-  for x in boundary.min_x..=boundary.max_x {
-    for y in boundary.min_y..=boundary.max_y {
-      let mut point = Coord { x, y };
-      let vec_idx = get_closest_vector_idx_if_one_for_point(coords, &mut point);
-
-      if let Some(v) = vec_idx {
-        let counter = point_idx_to_count.entry(v).or_insert(0);
-        *counter += 1;
-
-        if boundary.is_point_on_edge(&mut point) {
-          impossible_index.insert(v);
-        }
-      }
-    }
-  }
-
-  let mut current_area: Option<usize> = None;
-
-  for key in point_idx_to_count.keys() {
-    let val = &point_idx_to_count[key];
-
-    if impossible_index.contains(key) {
-      continue;
-    }
-
-    if current_area.is_none() || current_area.unwrap() < *val {
-      current_area = Some(*val);
-    }
-  }
-
-  current_area
-}
-
-fn get_region_area_with_total_distance_smaller_than(
-  coords: &mut Vec<Coord>,
-  max_distance: usize,
-) -> usize {
-  let boundary = get_coords_boundary(coords);
-  let mut area = 0;
-
-  for x in boundary.min_x..=boundary.max_x {
-    for y in boundary.min_y..=boundary.max_y {
-      let point = Coord { x, y };
-      let mut total_distance = 0;
-
-      for (_, coord) in coords.iter_mut().enumerate() {
-        let distance = point.calc_manhattan_distance(coord);
-        total_distance += distance;
-
-        if total_distance > max_distance {
-          break;
-        }
-      }
+const EXERCISE_MAX_DISTANCE: usize = 10000;
 
-      if total_distance < max_distance {
-        area += 1;
-      }
-    }
-  }
+fn write_regions_file(coords: &[Coord], region_map: &RegionMap) {
+  let mut file = File::create("regions.txt").expect("Unable to create the file");
 
-  area
+  file
+    .write_all(format!("{}\n", region_map.render(coords)).as_bytes())
+    .expect("Unable to write the file");
 }
 
-const EXERCISE_MAX_DISTANCE: usize = 10000;
-
 fn main() {
-  let mut coords = get_input_coords();
-  let biggest_finite_area = get_biggest_finite_area(&mut coords);
-  let biggest_area_of_distance =
-    get_region_area_with_total_distance_smaller_than(&mut coords, EXERCISE_MAX_DISTANCE);
-
-  if biggest_finite_area.is_none() {
-    panic!("Unexpected non-result");
-  }
+  let coords = get_input_coords();
+  let region_map = RegionMap::new(&coords, Metric::Manhattan, 0);
+  let biggest_area_of_distance = get_region_area(&coords, EXERCISE_MAX_DISTANCE);
+  let args: Vec<String> = env::args().skip(1).collect();
 
   println!("Results:");
   println!(
     "- (1) biggest finite area: {}",
-    biggest_finite_area.unwrap()
+    region_map
+      .get_biggest_finite_area()
+      .expect("Unexpected non-result")
   );
   println!(
     "- (2) biggest area with distance: {}",
     biggest_area_of_distance
   );
+
+  for metric in &[Metric::Chebyshev, Metric::Euclidean] {
+    println!(
+      "Biggest finite area with {:?} distance: {:?}",
+      metric,
+      RegionMap::new(&coords, *metric, 0).get_biggest_finite_area()
+    );
+  }
+
+  // `cargo run -- regions`
+  if args.first().map(|x| x.as_ref()) == Some("regions") {
+    write_regions_file(&coords, &region_map);
+    println!("Wrote the regions in regions.txt");
+  }
 }
 
 #[cfg(test)]
@@ -357,8 +276,8 @@ mod tests {
 
   #[test]
   fn test_get_coords_boundary() {
-    let mut data = get_example_1_data();
-    let result = get_coords_boundary(&mut data);
+    let data = get_example_1_data();
+    let result = get_coords_boundary(&data);
 
     assert_eq!(
       result,
@@ -370,35 +289,4 @@ mod tests {
       }
     );
   }
-
-  #[test]
-  fn test_get_closest_vector_idx_if_one_for_point() {
-    fn test_this(point: Coord, expected_result: Option<usize>) {
-      let mut point = point;
-      let mut data = get_example_1_data();
-      let result = get_closest_vector_idx_if_one_for_point(&mut data, &mut point);
-
-      assert_eq!(result, expected_result);
-    }
-
-    test_this(Coord { x: 0, y: 0 }, Some(0));
-    test_this(Coord { x: 8, y: 10 }, Some(5));
-    test_this(Coord { x: 8, y: 9 }, Some(5));
-  }
-
-  #[test]
-  fn test_get_biggest_finite_area() {
-    let mut data = get_example_1_data();
-    let result = get_biggest_finite_area(&mut data).unwrap();
-
-    assert_eq!(result, 17);
-  }
-
-  #[test]
-  fn test_get_region_area_with_total_distance_smaller_than() {
-    let mut data = get_example_1_data();
-    let result = get_region_area_with_total_distance_smaller_than(&mut data, 32);
-
-    assert_eq!(result, 16);
-  }
 }
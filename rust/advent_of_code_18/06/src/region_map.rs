@@ -0,0 +1,545 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+use crate::{get_coords_boundary, Coord, CoordUnit};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Metric {
+  Manhattan,
+  Chebyshev,
+  Euclidean,
+}
+
+impl Metric {
+  // the squared distance for Euclidean, which keeps the order while staying an integer
+  pub fn get_distance(&self, a: &Coord, b: &Coord) -> u64 {
+    let dx = u64::from((a.x - b.x).unsigned_abs());
+    let dy = u64::from((a.y - b.y).unsigned_abs());
+
+    match self {
+      Metric::Manhattan => dx + dy,
+      Metric::Chebyshev => dx.max(dy),
+      Metric::Euclidean => dx * dx + dy * dy,
+    }
+  }
+
+  // the steps that get one unit closer to a coordinate
+  fn get_neighbour_offsets(&self) -> &'static [(CoordUnit, CoordUnit)] {
+    match self {
+      Metric::Manhattan => &[(1, 0), (-1, 0), (0, 1), (0, -1)],
+      Metric::Chebyshev | Metric::Euclidean => &[
+        (1, 0),
+        (-1, 0),
+        (0, 1),
+        (0, -1),
+        (1, 1),
+        (1, -1),
+        (-1, 1),
+        (-1, -1),
+      ],
+    }
+  }
+}
+
+type Location = (CoordUnit, CoordUnit);
+
+// The closest coordinate of the locations of a window that contains the coordinates, where `None`
+// is a tie. With Euclidean the finite regions can go out of the window, so their locations outside
+// it are also included
+#[derive(Debug, Clone, PartialEq)]
+pub struct RegionMap {
+  pub min_x: CoordUnit,
+  pub min_y: CoordUnit,
+  pub width: usize,
+  pub height: usize,
+  owners: HashMap<Location, Option<usize>>,
+  infinite: Vec<bool>,
+}
+
+// Andrew's monotone chain, keeping the coordinates in the middle of the edges and the repeated ones
+fn get_convex_hull_idxs(coords: &[Coord]) -> Vec<usize> {
+  let mut sorted_idxs: Vec<usize> = (0..coords.len()).collect();
+
+  sorted_idxs.sort_by_key(|idx| (coords[*idx].x, coords[*idx].y));
+  sorted_idxs.dedup_by_key(|idx| (coords[*idx].x, coords[*idx].y));
+
+  if sorted_idxs.len() < 3 {
+    return get_idxs_at_same_locations(coords, &sorted_idxs);
+  }
+
+  let cross = |o: usize, a: usize, b: usize| -> i64 {
+    let (o, a, b) = (&coords[o], &coords[a], &coords[b]);
+
+    i64::from(a.x - o.x) * i64::from(b.y - o.y) - i64::from(a.y - o.y) * i64::from(b.x - o.x)
+  };
+  let mut hull: Vec<usize> = vec![];
+
+  for pass_idxs in [sorted_idxs.clone(), sorted_idxs.into_iter().rev().collect()] {
+    let mut chain: Vec<usize> = vec![];
+
+    for idx in pass_idxs {
+      while chain.len() >= 2 && cross(chain[chain.len() - 2], chain[chain.len() - 1], idx) < 0 {
+        chain.pop();
+      }
+
+      chain.push(idx);
+    }
+
+    hull.extend(chain);
+  }
+
+  get_idxs_at_same_locations(coords, &hull)
+}
+
+// the repetitions of a coordinate are in the hull when the first one is
+fn get_idxs_at_same_locations(coords: &[Coord], idxs: &[usize]) -> Vec<usize> {
+  (0..coords.len())
+    .filter(|idx| {
+      idxs
+        .iter()
+        .any(|other_idx| coords[*other_idx] == coords[*idx])
+    })
+    .collect()
+}
+
+impl RegionMap {
+  // Multi-source flood from the coordinates, visiting the locations by their distance. With
+  // Manhattan and Chebyshev the closest coordinates of a location are among the ones of its
+  // neighbours, because the step towards its closest coordinate gets one unit closer to it. That
+  // doesn't hold on the grid with Euclidean, so it falls back to comparing every location with all
+  // the coordinates, in O(area * n), and also floods the finite regions out of the window
+  pub fn new(coords: &[Coord], metric: Metric, margin: usize) -> RegionMap {
+    let boundary = get_coords_boundary(coords);
+    // with Chebyshev the regions can be finite outside the boundary, up to half its size
+    let metric_margin = match metric {
+      Metric::Chebyshev => {
+        ((boundary.max_x - boundary.min_x).max(boundary.max_y - boundary.min_y) / 2 + 1) as usize
+      }
+      Metric::Manhattan | Metric::Euclidean => 0,
+    };
+    let full_margin = (margin + metric_margin) as CoordUnit;
+    let min_x = boundary.min_x - full_margin;
+    let min_y = boundary.min_y - full_margin;
+    let max_x = boundary.max_x + full_margin;
+    let max_y = boundary.max_y + full_margin;
+    let is_in_window = |(x, y): Location| x >= min_x && x <= max_x && y >= min_y && y <= max_y;
+    let hull_idxs = get_convex_hull_idxs(coords);
+    let mut distances: HashMap<Location, u64> = HashMap::new();
+    let mut closest: HashMap<Location, Vec<usize>> = HashMap::new();
+    let mut settled: HashSet<Location> = HashSet::new();
+    let mut queue: BinaryHeap<Reverse<(u64, Location)>> = BinaryHeap::new();
+
+    for (idx, coord) in coords.iter().enumerate() {
+      let location = (coord.x, coord.y);
+
+      distances.insert(location, 0);
+      closest.entry(location).or_default().push(idx);
+      queue.push(Reverse((0, location)));
+    }
+
+    while let Some(Reverse((_, location))) = queue.pop() {
+      if !settled.insert(location) {
+        continue;
+      }
+
+      let location_closest = closest[&location].clone();
+      let follows_finite_region =
+        metric == Metric::Euclidean && location_closest.iter().any(|idx| !hull_idxs.contains(idx));
+
+      for (dx, dy) in metric.get_neighbour_offsets() {
+        let next = (location.0 + dx, location.1 + dy);
+
+        if settled.contains(&next) {
+          continue;
+        }
+
+        let candidates: Vec<usize> = if metric == Metric::Euclidean {
+          if distances.contains_key(&next) || !(is_in_window(next) || follows_finite_region) {
+            continue;
+          }
+
+          (0..coords.len()).collect()
+        } else if is_in_window(next) {
+          location_closest.clone()
+        } else {
+          continue;
+        };
+        let next_coord = Coord {
+          x: next.0,
+          y: next.1,
+        };
+        let mut improved = false;
+
+        for idx in candidates {
+          let distance = metric.get_distance(&next_coord, &coords[idx]);
+
+          match distances.get(&next) {
+            Some(current) if *current < distance => {}
+            Some(current) if *current == distance => {
+              let next_closest = closest.entry(next).or_default();
+
+              if !next_closest.contains(&idx) {
+                next_closest.push(idx);
+              }
+            }
+            _ => {
+              distances.insert(next, distance);
+              closest.insert(next, vec![idx]);
+              improved = true;
+            }
+          }
+        }
+
+        if improved {
+          queue.push(Reverse((distances[&next], next)));
+        }
+      }
+    }
+
+    let owners: HashMap<Location, Option<usize>> = closest
+      .into_iter()
+      .filter(|(location, location_closest)| {
+        is_in_window(*location) || location_closest.iter().any(|idx| !hull_idxs.contains(idx))
+      })
+      .map(|(location, location_closest)| match location_closest[..] {
+        [idx] => (location, Some(idx)),
+        _ => (location, None),
+      })
+      .collect();
+    let mut region_map = RegionMap {
+      min_x,
+      min_y,
+      width: (max_x - min_x + 1) as usize,
+      height: (max_y - min_y + 1) as usize,
+      owners,
+      infinite: vec![false; coords.len()],
+    };
+
+    region_map.infinite = match metric {
+      Metric::Euclidean => {
+        let mut infinite = vec![false; coords.len()];
+
+        for idx in hull_idxs {
+          infinite[idx] = true;
+        }
+
+        infinite
+      }
+      Metric::Manhattan | Metric::Chebyshev => region_map.get_infinite_regions(coords, metric),
+    };
+
+    region_map
+  }
+
+  // A location past the farthest coordinate in one of the directions gets further from every
+  // coordinate by the same amount when moving in that direction, so it keeps its owner forever
+  fn get_infinite_regions(&self, coords: &[Coord], metric: Metric) -> Vec<bool> {
+    let mut infinite = vec![false; coords.len()];
+    let directions: &[(CoordUnit, CoordUnit)] = match metric {
+      Metric::Chebyshev => &[(1, 1), (1, -1), (-1, 1), (-1, -1)],
+      _ => &[(1, 0), (-1, 0), (0, 1), (0, -1)],
+    };
+
+    for (dx, dy) in directions {
+      let project = |(x, y): Location| x * dx + y * dy;
+      let max_projection = coords
+        .iter()
+        .map(|coord| project((coord.x, coord.y)))
+        .max()
+        .unwrap_or(0);
+
+      for (location, owner) in self.owners.iter() {
+        if let Some(idx) = owner {
+          if project(*location) >= max_projection {
+            infinite[*idx] = true;
+          }
+        }
+      }
+    }
+
+    infinite
+  }
+
+  pub fn get_owner(&self, location: &Coord) -> Option<usize> {
+    self
+      .owners
+      .get(&(location.x, location.y))
+      .cloned()
+      .flatten()
+  }
+
+  // the Euclidean unbounded regions are the ones of the coordinates in the convex hull
+  pub fn is_infinite(&self, idx: usize) -> bool {
+    self.infinite[idx]
+  }
+
+  // the infinite regions only count their locations inside the window
+  pub fn get_areas(&self) -> Vec<usize> {
+    let mut areas = vec![0; self.infinite.len()];
+
+    for idx in self.owners.values().flatten() {
+      areas[*idx] += 1;
+    }
+
+    areas
+  }
+
+  pub fn get_biggest_finite_area(&self) -> Option<usize> {
+    self
+      .get_areas()
+      .into_iter()
+      .enumerate()
+      .filter(|(idx, _)| !self.is_infinite(*idx))
+      .map(|(_, area)| area)
+      .max()
+  }
+
+  // like the example of the puzzle, for the window: the coordinates in uppercase, their regions in
+  // lowercase (the letters repeat every 26 coordinates) and the ties as `.`
+  pub fn render(&self, coords: &[Coord]) -> String {
+    let mut rows: Vec<Vec<char>> = (0..self.height as CoordUnit)
+      .map(|y| {
+        (0..self.width as CoordUnit)
+          .map(|x| {
+            let location = Coord {
+              x: x + self.min_x,
+              y: y + self.min_y,
+            };
+
+            match self.get_owner(&location) {
+              Some(idx) => (b'a' + (idx % 26) as u8) as char,
+              None => '.',
+            }
+          })
+          .collect()
+      })
+      .collect();
+
+    for (idx, coord) in coords.iter().enumerate() {
+      let x = (coord.x - self.min_x) as usize;
+      let y = (coord.y - self.min_y) as usize;
+
+      rows[y][x] = (b'A' + (idx % 26) as u8) as char;
+    }
+
+    rows
+      .iter()
+      .map(|row| row.iter().collect::<String>())
+      .collect::<Vec<String>>()
+      .join("\n")
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn get_example_coords() -> Vec<Coord> {
+    [(1, 1), (1, 6), (8, 3), (3, 4), (5, 5), (8, 9)]
+      .iter()
+      .map(|(x, y)| Coord::new(*x, *y))
+      .collect()
+  }
+
+  // compares every location with every coordinate
+  fn get_owner_brute_force(coords: &[Coord], metric: Metric, location: &Coord) -> Option<usize> {
+    let distances: Vec<u64> = coords
+      .iter()
+      .map(|coord| metric.get_distance(location, coord))
+      .collect();
+    let min_distance = *distances.iter().min().unwrap();
+    let closest: Vec<usize> = (0..coords.len())
+      .filter(|idx| distances[*idx] == min_distance)
+      .collect();
+
+    match closest[..] {
+      [idx] => Some(idx),
+      _ => None,
+    }
+  }
+
+  #[test]
+  fn test_get_distance() {
+    let (a, b) = (Coord::new(-2, 1), Coord::new(1, 5));
+
+    assert_eq!(Metric::Manhattan.get_distance(&a, &b), 7);
+    assert_eq!(Metric::Chebyshev.get_distance(&a, &b), 4);
+    assert_eq!(Metric::Euclidean.get_distance(&a, &b), 25);
+  }
+
+  #[test]
+  fn test_example() {
+    let coords = get_example_coords();
+    let region_map = RegionMap::new(&coords, Metric::Manhattan, 1);
+    let rendered = region_map.render(&coords);
+    let lines: Vec<&str> = rendered.lines().collect();
+
+    assert_eq!(
+      lines[..10].to_vec(),
+      vec![
+        "aaaaa.cccc",
+        "aAaaa.cccc",
+        "aaaddecccc",
+        "aadddeccCc",
+        "..dDdeeccc",
+        "bb.deEeecc",
+        "bBb.eeee..",
+        "bbb.eeefff",
+        "bbb.eeffff",
+        "bbb.ffffFf",
+      ]
+    );
+    assert_eq!(region_map.get_areas()[3..5].to_vec(), vec![9, 17]);
+    assert_eq!(
+      (0..coords.len())
+        .map(|idx| region_map.is_infinite(idx))
+        .collect::<Vec<bool>>(),
+      vec![true, true, true, false, false, true]
+    );
+    assert_eq!(region_map.get_biggest_finite_area(), Some(17));
+  }
+
+  #[test]
+  fn test_get_convex_hull_idxs() {
+    let coords: Vec<Coord> = [(0, 0), (2, 0), (4, 0), (2, 2), (4, 4), (1, 3), (0, 4)]
+      .iter()
+      .map(|(x, y)| Coord::new(*x, *y))
+      .collect();
+
+    assert_eq!(get_convex_hull_idxs(&coords), vec![0, 1, 2, 4, 6]);
+
+    let repeated_coords: Vec<Coord> = [(0, 0), (0, 0), (10, 0), (0, 10), (2, 2), (2, 2)]
+      .iter()
+      .map(|(x, y)| Coord::new(*x, *y))
+      .collect();
+
+    assert_eq!(get_convex_hull_idxs(&repeated_coords), vec![0, 1, 2, 3]);
+    assert_eq!(get_convex_hull_idxs(&repeated_coords[..2]), vec![0, 1]);
+  }
+
+  // the repeated coordinates tie everywhere in their region, which doesn't stop the Euclidean flood
+  // when they are in the hull
+  #[test]
+  fn test_repeated_coords() {
+    let coords: Vec<Coord> = [(0, 0), (0, 0), (10, 0), (0, 10), (3, 3), (3, 3)]
+      .iter()
+      .map(|(x, y)| Coord::new(*x, *y))
+      .collect();
+
+    for metric in &[Metric::Manhattan, Metric::Chebyshev, Metric::Euclidean] {
+      let region_map = RegionMap::new(&coords, *metric, 0);
+
+      assert_eq!(region_map.get_owner(&Coord::new(0, 0)), None);
+      assert_eq!(region_map.get_owner(&Coord::new(3, 3)), None);
+      assert_eq!(region_map.get_areas()[2], region_map.get_areas()[3]);
+      assert_eq!(region_map.get_areas()[..2].to_vec(), vec![0, 0]);
+    }
+  }
+
+  fn get_window_locations(region_map: &RegionMap) -> Vec<Coord> {
+    let mut locations = vec![];
+
+    for y in region_map.min_y..region_map.min_y + region_map.height as CoordUnit {
+      for x in region_map.min_x..region_map.min_x + region_map.width as CoordUnit {
+        locations.push(Coord::new(x, y));
+      }
+    }
+
+    locations
+  }
+
+  // The flood gives the same owners as the brute force. The finite regions don't reach the border
+  // of a bigger window, and with Euclidean their locations out of the window are the ones of the
+  // brute force, with no neighbour of the same region left out
+  #[test]
+  fn test_coord_sets() {
+    let coord_sets: [&[(CoordUnit, CoordUnit)]; 7] = [
+      &[(1, 1), (1, 6), (8, 3), (3, 4), (5, 5), (8, 9)],
+      // on a line, on a diagonal and on a square
+      &[(0, 0), (4, 0), (9, 0), (12, 0)],
+      &[(0, 0), (3, 3), (6, 6), (2, 7)],
+      &[(0, 0), (10, 0), (0, 10), (10, 10), (5, 5)],
+      // one surrounded by a ring, with ties between the ring ones
+      &[(5, 5), (5, 0), (0, 5), (10, 5), (5, 10), (1, 1), (9, 9)],
+      // repeated and contiguous coordinates
+      &[(2, 2), (2, 2), (3, 2), (-4, 7), (8, -3)],
+      &[
+        (-5, -5),
+        (14, -5),
+        (-5, 14),
+        (14, 14),
+        (4, 4),
+        (5, 4),
+        (4, 6),
+      ],
+    ];
+
+    for coord_set in coord_sets.iter() {
+      let coords: Vec<Coord> = coord_set.iter().map(|(x, y)| Coord::new(*x, *y)).collect();
+
+      for metric in &[Metric::Manhattan, Metric::Chebyshev, Metric::Euclidean] {
+        let region_map = RegionMap::new(&coords, *metric, 15);
+        let mut on_border = vec![false; coords.len()];
+
+        for location in get_window_locations(&region_map) {
+          let owner = region_map.get_owner(&location);
+          let is_border = location.x == region_map.min_x
+            || location.y == region_map.min_y
+            || location.x == region_map.min_x + region_map.width as CoordUnit - 1
+            || location.y == region_map.min_y + region_map.height as CoordUnit - 1;
+
+          assert_eq!(
+            owner,
+            get_owner_brute_force(&coords, *metric, &location),
+            "{:?} {:?} {:?}",
+            metric,
+            coords,
+            location
+          );
+
+          if let (true, Some(idx)) = (is_border, owner) {
+            on_border[idx] = true;
+          }
+        }
+
+        if *metric == Metric::Euclidean {
+          for (location, owner) in region_map.owners.iter() {
+            let idx = match owner {
+              Some(idx) if !region_map.is_infinite(*idx) => *idx,
+              _ => continue,
+            };
+            let location = Coord::new(location.0, location.1);
+
+            assert_eq!(
+              get_owner_brute_force(&coords, *metric, &location),
+              Some(idx),
+              "{:?} {:?}",
+              coords,
+              location
+            );
+
+            for (dx, dy) in metric.get_neighbour_offsets() {
+              let neighbour = Coord::new(location.x + dx, location.y + dy);
+
+              if !region_map.owners.contains_key(&(neighbour.x, neighbour.y)) {
+                assert_ne!(
+                  get_owner_brute_force(&coords, *metric, &neighbour),
+                  Some(idx),
+                  "{:?} {:?}",
+                  coords,
+                  neighbour
+                );
+              }
+            }
+          }
+        } else {
+          for (idx, reaches_border) in on_border.iter().enumerate() {
+            if *reaches_border {
+              assert!(region_map.is_infinite(idx), "{:?} {:?}", metric, coords);
+            }
+          }
+        }
+      }
+    }
+  }
+}
@@ -0,0 +1,215 @@
+use crate::{Coord, CoordUnit};
+
+// The sums of the distances to the coordinates along one axis, for the consecutive positions from
+// `start` where the sum is smaller than the limit
+#[derive(Debug, PartialEq)]
+pub struct AxisDistances {
+  pub start: CoordUnit,
+  pub sums: Vec<i64>,
+}
+
+// The positions of the coordinates in one axis, sorted, with their prefix sums
+struct SortedAxis {
+  positions: Vec<i64>,
+  prefix_sums: Vec<i64>,
+}
+
+impl SortedAxis {
+  fn new(mut positions: Vec<i64>) -> SortedAxis {
+    positions.sort_unstable();
+
+    let mut prefix_sums = vec![0];
+
+    for position in positions.iter() {
+      prefix_sums.push(prefix_sums[prefix_sums.len() - 1] + position);
+    }
+
+    SortedAxis {
+      positions,
+      prefix_sums,
+    }
+  }
+
+  // the positions before `position` add `position - p` and the rest add `p - position`
+  fn get_distances_sum(&self, position: i64) -> i64 {
+    let before = self.positions.partition_point(|p| *p < position);
+    let after = self.positions.len() - before;
+    let total = self.prefix_sums[self.positions.len()];
+
+    position * before as i64 - self.prefix_sums[before] + (total - self.prefix_sums[before])
+      - position * after as i64
+  }
+
+  // the sum is convex and has the minimum at the median
+  fn get_median(&self) -> i64 {
+    self.positions[self.positions.len() / 2]
+  }
+
+  // grows the range from the median in both directions while the sum is smaller than the limit
+  fn get_axis_distances(&self, limit: i64) -> AxisDistances {
+    let median = self.get_median();
+    let mut start = median;
+    let mut end = median;
+
+    if self.get_distances_sum(median) >= limit {
+      return AxisDistances {
+        start: median as CoordUnit,
+        sums: vec![],
+      };
+    }
+
+    while self.get_distances_sum(start - 1) < limit {
+      start -= 1;
+    }
+
+    while self.get_distances_sum(end + 1) < limit {
+      end += 1;
+    }
+
+    AxisDistances {
+      start: start as CoordUnit,
+      sums: (start..=end)
+        .map(|position| self.get_distances_sum(position))
+        .collect(),
+    }
+  }
+}
+
+// The Manhattan total distance of a location is the sum of the one of its `x` and the one of its
+// `y`, so a location can only be in the region when the sum of its axis plus the minimum of the
+// other axis is smaller than the maximum. That gives the window of the region, which can go beyond
+// the boundary of the coordinates for big maximums
+pub fn get_region_window(coords: &[Coord], max_distance: usize) -> (AxisDistances, AxisDistances) {
+  let max_distance = max_distance as i64;
+  let x_axis = SortedAxis::new(coords.iter().map(|coord| i64::from(coord.x)).collect());
+  let y_axis = SortedAxis::new(coords.iter().map(|coord| i64::from(coord.y)).collect());
+  let min_x_sum = x_axis.get_distances_sum(x_axis.get_median());
+  let min_y_sum = y_axis.get_distances_sum(y_axis.get_median());
+
+  (
+    x_axis.get_axis_distances(max_distance - min_y_sum),
+    y_axis.get_axis_distances(max_distance - min_x_sum),
+  )
+}
+
+// With the sums of one axis sorted, each position of the other axis counts the ones that fit in
+// the rest of the maximum
+pub fn get_region_area(coords: &[Coord], max_distance: usize) -> usize {
+  if coords.is_empty() {
+    return 0;
+  }
+
+  let (x_distances, y_distances) = get_region_window(coords, max_distance);
+  let mut y_sums = y_distances.sums;
+
+  y_sums.sort_unstable();
+
+  x_distances
+    .sums
+    .iter()
+    .map(|x_sum| y_sums.partition_point(|y_sum| x_sum + y_sum < max_distance as i64))
+    .sum()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn to_coords(points: &[(CoordUnit, CoordUnit)]) -> Vec<Coord> {
+    points.iter().map(|(x, y)| Coord::new(*x, *y)).collect()
+  }
+
+  // the previous implementation in the new window, which adds the distances of every location
+  fn get_region_area_by_scan(coords: &[Coord], max_distance: usize) -> usize {
+    let (x_distances, y_distances) = get_region_window(coords, max_distance);
+    let mut area = 0;
+
+    for x in x_distances.start..x_distances.start + x_distances.sums.len() as CoordUnit {
+      for y in y_distances.start..y_distances.start + y_distances.sums.len() as CoordUnit {
+        let point = Coord { x, y };
+        let total_distance: usize = coords
+          .iter()
+          .map(|coord| point.calc_manhattan_distance(coord))
+          .sum();
+
+        if total_distance < max_distance {
+          area += 1;
+        }
+      }
+    }
+
+    area
+  }
+
+  #[test]
+  fn test_get_distances_sum() {
+    let axis = SortedAxis::new(vec![5, 1, 8]);
+
+    assert_eq!(axis.get_distances_sum(5), 7);
+    assert_eq!(axis.get_distances_sum(0), 14);
+    assert_eq!(axis.get_distances_sum(10), 16);
+  }
+
+  #[test]
+  fn test_example() {
+    let coords = to_coords(&[(1, 1), (1, 6), (8, 3), (3, 4), (5, 5), (8, 9)]);
+
+    assert_eq!(get_region_area(&coords, 32), 16);
+    assert_eq!(get_region_area_by_scan(&coords, 32), 16);
+  }
+
+  #[test]
+  fn test_region_beyond_boundary() {
+    let coords = to_coords(&[(0, 0), (1, 1)]);
+    let (x_distances, y_distances) = get_region_window(&coords, 6);
+
+    // e.g. (-1, 0) is at 1 from (0, 0) and at 3 from (1, 1), 3 in `x` and 1 in `y`
+    assert_eq!(
+      x_distances,
+      AxisDistances {
+        start: -1,
+        sums: vec![3, 1, 1, 3],
+      }
+    );
+    assert_eq!(y_distances, x_distances);
+    assert_eq!(get_region_area(&coords, 6), 12);
+    assert_eq!(get_region_area(&coords, 1), 0);
+  }
+
+  // compares with the scan of a window that is big enough for the maximums
+  #[test]
+  fn test_coord_sets() {
+    let coord_sets: [&[(CoordUnit, CoordUnit)]; 5] = [
+      &[(0, 0)],
+      &[(-10, -10), (9, 9)],
+      &[(1, 1), (1, 6), (8, 3), (3, 4), (5, 5), (8, 9)],
+      &[(3, -2), (3, -2), (-7, 4)],
+      &[(-10, 0), (9, 0), (0, -10), (0, 9), (2, 2), (-3, 5)],
+    ];
+
+    for coord_set in coord_sets.iter() {
+      let coords = to_coords(coord_set);
+
+      for max_distance in (0..60).step_by(7) {
+        let mut expected = 0;
+
+        for x in -75..75 {
+          for y in -75..75 {
+            let point = Coord { x, y };
+            let total_distance: usize = coords
+              .iter()
+              .map(|coord| point.calc_manhattan_distance(coord))
+              .sum();
+
+            if total_distance < max_distance {
+              expected += 1;
+            }
+          }
+        }
+
+        assert_eq!(get_region_area(&coords, max_distance), expected);
+        assert_eq!(get_region_area_by_scan(&coords, max_distance), expected);
+      }
+    }
+  }
+}
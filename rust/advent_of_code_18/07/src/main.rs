@@ -112,224 +112,117 @@ all of the steps?
 
 */
 
+mod task_graph;
+
 use regex::Regex;
-use std::collections::HashMap;
-use std::collections::HashSet;
+use std::env;
 use std::fs::File;
 use std::io::prelude::*;
-use std::iter::FromIterator;
-
-type DepsGraphItemId = char;
+use task_graph::{Schedule, Seconds, TaskGraph, TaskId};
 
-#[derive(Debug, PartialEq, Eq, Clone)]
-struct DepsGraphProps {
-  dependencies: HashSet<DepsGraphItemId>,
-  dependants: HashSet<DepsGraphItemId>,
-}
+const WORKERS: usize = 5;
+const EXTRA_SECONDS_PER_TASK: Seconds = 60;
 
-type DepsGraph = HashMap<DepsGraphItemId, DepsGraphProps>;
+fn create_task_graph_from_strs<F>(strs: &[&str], get_duration: F) -> Result<TaskGraph, String>
+where
+  F: Fn(&str) -> Result<Seconds, String>,
+{
+  let mut graph = TaskGraph::new();
+  let reg = Regex::new(r"^Step (\S+) must be finished before step (\S+) can begin.$").unwrap();
 
-fn create_deps_graph_from_strs(strs: &mut Vec<&str>) -> DepsGraph {
-  let mut graph: DepsGraph = HashMap::new();
+  for str_item in strs {
+    let caps = reg
+      .captures(str_item)
+      .ok_or(format!("Invalid instruction: {}", str_item))?;
 
-  let reg = Regex::new(r"^Step (.) must be finished before step (.) can begin.$").unwrap();
+    graph.add_dependency(&caps[1], &caps[2]);
+  }
 
-  for str_item in strs {
-    let caps = reg.captures(str_item).unwrap();
-    let item_id = caps.get(1).unwrap().as_str().chars().next().unwrap();
-    let dependant = caps.get(2).unwrap().as_str().chars().next().unwrap();
-
-    {
-      let item = graph.entry(item_id).or_insert(DepsGraphProps {
-        dependencies: HashSet::new(),
-        dependants: HashSet::new(),
-      });
-
-      item.dependants.insert(dependant);
-    }
-
-    {
-      let dependant_item = graph.entry(dependant).or_insert(DepsGraphProps {
-        dependencies: HashSet::new(),
-        dependants: HashSet::new(),
-      });
-
-      dependant_item.dependencies.insert(item_id);
-    }
+  for id in graph.get_task_ids() {
+    graph.add_task(&id, get_duration(&id)?);
   }
 
-  graph
+  Ok(graph)
 }
 
-fn get_order_of_graph_instructions(graph: &mut DepsGraph) -> Vec<DepsGraphItemId> {
-  let mut graph = graph.clone();
-  let mut used_chars: HashSet<DepsGraphItemId> = HashSet::new();
-  let mut result: Vec<DepsGraphItemId> = vec![];
-
-  loop {
-    let mut items_without_dependencies: Vec<DepsGraphItemId> = vec![];
-
-    for graph_item_id in graph.keys() {
-      let graph_item = &graph[graph_item_id];
-
-      if graph_item.dependencies.is_empty() && !used_chars.contains(graph_item_id) {
-        items_without_dependencies.push(*graph_item_id);
-      }
-    }
-
-    if items_without_dependencies.is_empty() {
-      break;
-    }
-
-    items_without_dependencies.sort();
-
-    let chosen_char = items_without_dependencies.first().unwrap();
-
-    used_chars.insert(*chosen_char);
-    result.push(*chosen_char);
-
-    let dependants: Vec<DepsGraphItemId>;
-
-    {
-      let graph_item = graph.get_mut(chosen_char).unwrap();
-      dependants = graph_item.dependants.iter().cloned().collect();
-    }
-
-    for item_id in dependants {
-      let dependant_graph_item = graph.get_mut(&item_id).unwrap();
-
-      dependant_graph_item.dependencies.remove(&chosen_char);
-    }
-  }
-
-  result
+fn get_order_of_graph_instructions(graph: &TaskGraph) -> Vec<TaskId> {
+  graph.get_order().unwrap_or_else(|err| panic!("{}", err))
 }
 
-fn get_input_graph() -> DepsGraph {
+fn get_input_lines() -> Vec<String> {
   let mut file = File::open("src/input.txt").expect("Unable to open the file");
   let mut contents = String::new();
   file
     .read_to_string(&mut contents)
     .expect("Unable to read the file");
 
-  let mut lines: Vec<&str> = contents.lines().map(|x| x).collect();
-
-  create_deps_graph_from_strs(&mut lines)
-}
-
-fn get_seconds_for_char(c: char) -> usize {
-  (c.to_digit(36).unwrap() - 9) as usize
+  contents.lines().map(|x| x.to_string()).collect()
 }
 
-fn get_seconds_of_execution_with_n_workers(
-  graph: &mut DepsGraph,
-  workers: usize,
-  extra_secs_per_task: usize,
-) -> usize {
-  let mut graph = graph.clone();
-  let total_items = graph.keys().count();
-  let mut used_chars: HashSet<DepsGraphItemId> = HashSet::new();
-  let mut ellapsed_seconds = 0;
-  let mut temporally_without_items = false;
-
-  #[derive(Debug, PartialEq, Eq, Clone)]
-  struct Task {
-    c: char,
-    completed_seconds: usize,
-    total_seconds: usize,
+// The steps of the puzzle are single uppercase letters: A takes 1 second, B 2 seconds, etc. Any
+// other id can't be timed
+fn get_seconds_for_step(id: &str) -> Result<Seconds, String> {
+  match id.as_bytes() {
+    [letter @ b'A'..=b'Z'] => Ok((letter - b'A' + 1) as Seconds),
+    _ => Err(format!("Unable to time the step {}", id)),
   }
+}
 
-  let mut tasks: Vec<Task> = vec![];
-
-  loop {
-    let mut tasks_to_complete: Vec<usize> = vec![];
-
-    for (idx, task) in tasks.iter_mut().enumerate() {
-      task.completed_seconds += 1;
-
-      if task.completed_seconds == task.total_seconds {
-        tasks_to_complete.push(idx);
-
-        let dependants: Vec<DepsGraphItemId>;
-
-        {
-          let graph_item = graph.get_mut(&task.c).unwrap();
-          dependants = graph_item.dependants.iter().cloned().collect();
-        }
-
-        for item_id in dependants {
-          let dependant_graph_item = graph.get_mut(&item_id).unwrap();
-
-          dependant_graph_item.dependencies.remove(&task.c);
-        }
-      }
-    }
-
-    tasks_to_complete.reverse();
-
-    for task_idx in tasks_to_complete {
-      temporally_without_items = false;
-      tasks.remove(task_idx);
-    }
-
-    let current_tasks = tasks.len();
-    let free_workers = workers - current_tasks;
-
-    if free_workers > 0 && used_chars.len() < total_items && temporally_without_items != true {
-      let mut items_without_dependencies: Vec<DepsGraphItemId> = vec![];
-
-      for graph_item_id in graph.keys() {
-        let graph_item = &graph[graph_item_id];
-
-        if graph_item.dependencies.is_empty() && !used_chars.contains(graph_item_id) {
-          items_without_dependencies.push(*graph_item_id);
-        }
-      }
-
-      items_without_dependencies.sort();
-
-      for _ in 0..free_workers {
-        if items_without_dependencies.is_empty() {
-          temporally_without_items = true;
-          break;
-        }
-
-        let chosen_char = items_without_dependencies.pop().unwrap();
+fn get_input_graph(extra_secs_per_task: Seconds) -> TaskGraph {
+  let lines = get_input_lines();
+  let strs: Vec<&str> = lines.iter().map(|x| x.as_str()).collect();
 
-        used_chars.insert(chosen_char);
+  create_task_graph_from_strs(&strs, |id| {
+    get_seconds_for_step(id).map(|seconds| seconds + extra_secs_per_task)
+  })
+  .unwrap_or_else(|err| panic!("{}", err))
+}
 
-        tasks.push(Task {
-          c: chosen_char,
-          completed_seconds: 0,
-          total_seconds: get_seconds_for_char(chosen_char) + extra_secs_per_task,
-        })
-      }
-    } else if current_tasks == 0 {
-      break;
-    }
+fn get_schedule_with_n_workers(graph: &TaskGraph, workers: usize) -> Schedule {
+  graph
+    .schedule(workers)
+    .unwrap_or_else(|err| panic!("{}", err))
+}
 
-    ellapsed_seconds += 1;
-  }
+fn write_schedule_file(schedule: &Schedule) {
+  let mut file = File::create("schedule.txt").expect("Unable to create the file");
 
-  ellapsed_seconds
+  file
+    .write_all(schedule.to_gantt_string(120).as_bytes())
+    .expect("Unable to write the file");
 }
 
 fn main() {
-  let mut graph = get_input_graph();
-  let order = get_order_of_graph_instructions(&mut graph);
-  let seconds = get_seconds_of_execution_with_n_workers(&mut graph, 5, 60);
+  let graph = get_input_graph(EXTRA_SECONDS_PER_TASK);
+  let order = get_order_of_graph_instructions(&graph);
+  let schedule = get_schedule_with_n_workers(&graph, WORKERS);
+  let critical_path = graph
+    .get_critical_path()
+    .unwrap_or_else(|err| panic!("{}", err));
+  let args: Vec<String> = env::args().skip(1).collect();
 
   println!("Result:");
-  println!("- (1) order of instructions: {}", String::from_iter(order));
-  println!("- (2) seconds: {}", seconds);
+  println!("- (1) order of instructions: {}", order.concat());
+  println!("- (2) seconds: {}", schedule.total_time);
+  println!(
+    "- critical path: {} ({} seconds)",
+    critical_path.tasks.concat(),
+    critical_path.total_time
+  );
+
+  // `cargo run -- schedule`
+  if args.first().map(|x| x.as_ref()) == Some("schedule") {
+    write_schedule_file(&schedule);
+    println!("- wrote the schedule in schedule.txt");
+  }
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
 
-  fn create_example_graph() -> DepsGraph {
-    let mut items = vec![
+  fn create_example_graph() -> TaskGraph {
+    let items = vec![
       "Step C must be finished before step A can begin.",
       "Step C must be finished before step F can begin.",
       "Step A must be finished before step B can begin.",
@@ -339,22 +232,49 @@ mod tests {
       "Step F must be finished before step E can begin.",
     ];
 
-    create_deps_graph_from_strs(&mut items)
+    create_task_graph_from_strs(&items, get_seconds_for_step).unwrap()
   }
 
   #[test]
   fn test_get_order_of_graph_instructions() {
-    let mut graph = create_example_graph();
-    let order = get_order_of_graph_instructions(&mut graph);
+    let graph = create_example_graph();
+    let order = get_order_of_graph_instructions(&graph);
 
-    assert_eq!(String::from_iter(order), "CABDFE");
+    assert_eq!(order.concat(), "CABDFE");
   }
 
   #[test]
-  fn test_get_seconds_of_execution_with_n_workers() {
-    let mut graph = create_example_graph();
-    let seconds = get_seconds_of_execution_with_n_workers(&mut graph, 2, 0);
+  fn test_get_schedule_with_n_workers() {
+    let graph = create_example_graph();
+    let schedule = get_schedule_with_n_workers(&graph, 2);
 
-    assert_eq!(seconds, 15);
+    assert_eq!(schedule.total_time, 15);
+  }
+
+  #[test]
+  fn test_get_seconds_for_step() {
+    assert_eq!(get_seconds_for_step("A"), Ok(1));
+    assert_eq!(get_seconds_for_step("Z"), Ok(26));
+    assert!(get_seconds_for_step("z").is_err());
+    assert!(get_seconds_for_step("AB").is_err());
+    assert!(get_seconds_for_step("").is_err());
+    assert!(get_seconds_for_step("step-1").is_err());
+    assert!(get_seconds_for_step("0").is_err());
+    assert!(get_seconds_for_step("a_b").is_err());
+  }
+
+  #[test]
+  fn test_create_task_graph_with_invalid_steps() {
+    assert!(create_task_graph_from_strs(
+      &["Step AB must be finished before step C can begin."],
+      get_seconds_for_step
+    )
+    .is_err());
+    assert!(create_task_graph_from_strs(
+      &["Step A must be finished before step B-2 can begin."],
+      get_seconds_for_step
+    )
+    .is_err());
+    assert!(create_task_graph_from_strs(&["Step A before B"], get_seconds_for_step).is_err());
   }
 }
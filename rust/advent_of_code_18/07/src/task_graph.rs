@@ -0,0 +1,446 @@
+use std::cmp::max;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt;
+
+pub type TaskId = String;
+pub type Seconds = usize;
+
+#[derive(Debug, Clone, Default)]
+struct Task {
+  duration: Seconds,
+  dependencies: BTreeSet<TaskId>,
+  dependants: BTreeSet<TaskId>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CycleError {
+  // the first task is repeated at the end
+  pub cycle: Vec<TaskId>,
+}
+
+impl fmt::Display for CycleError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "the tasks have a cycle: {}", self.cycle.join(" -> "))
+  }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScheduledTask {
+  pub id: TaskId,
+  pub worker: usize,
+  pub start: Seconds,
+  pub end: Seconds,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Schedule {
+  pub tasks: Vec<ScheduledTask>,
+  pub total_time: Seconds,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CriticalPath {
+  pub tasks: Vec<TaskId>,
+  pub total_time: Seconds,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TaskGraph {
+  tasks: BTreeMap<TaskId, Task>,
+}
+
+impl TaskGraph {
+  pub fn new() -> TaskGraph {
+    TaskGraph::default()
+  }
+
+  // it also updates the duration of an existing task
+  pub fn add_task(&mut self, id: &str, duration: Seconds) {
+    self.tasks.entry(id.to_string()).or_default().duration = duration;
+  }
+
+  // the tasks that don't exist yet are added with a duration of zero
+  pub fn add_dependency(&mut self, before: &str, after: &str) {
+    self
+      .tasks
+      .entry(before.to_string())
+      .or_default()
+      .dependants
+      .insert(after.to_string());
+    self
+      .tasks
+      .entry(after.to_string())
+      .or_default()
+      .dependencies
+      .insert(before.to_string());
+  }
+
+  pub fn get_task_ids(&self) -> Vec<TaskId> {
+    self.tasks.keys().cloned().collect()
+  }
+
+  // iterative depth-first search following the dependants
+  fn find_cycle(&self) -> Option<Vec<TaskId>> {
+    let mut done: HashSet<&TaskId> = HashSet::new();
+    let mut in_progress: HashSet<&TaskId> = HashSet::new();
+
+    for start in self.tasks.keys() {
+      if done.contains(start) {
+        continue;
+      }
+
+      let mut stack = vec![(start, self.tasks[start].dependants.iter())];
+
+      in_progress.insert(start);
+
+      while let Some((id, dependants)) = stack.last_mut() {
+        match dependants.next() {
+          Some(dependant) => {
+            if in_progress.contains(dependant) {
+              let cycle_start = stack.iter().position(|(x, _)| *x == dependant).unwrap();
+              let mut cycle: Vec<TaskId> = stack[cycle_start..]
+                .iter()
+                .map(|(x, _)| x.to_string())
+                .collect();
+
+              cycle.push(dependant.to_string());
+
+              return Some(cycle);
+            }
+
+            if !done.contains(dependant) {
+              in_progress.insert(dependant);
+              stack.push((dependant, self.tasks[dependant].dependants.iter()));
+            }
+          }
+          None => {
+            let id: &TaskId = id;
+
+            in_progress.remove(id);
+            done.insert(id);
+            stack.pop();
+          }
+        }
+      }
+    }
+
+    None
+  }
+
+  pub fn check_acyclic(&self) -> Result<(), CycleError> {
+    match self.find_cycle() {
+      Some(cycle) => Err(CycleError { cycle }),
+      None => Ok(()),
+    }
+  }
+
+  fn get_pending_dependencies_nums(&self) -> HashMap<&TaskId, usize> {
+    self
+      .tasks
+      .iter()
+      .map(|(id, task)| (id, task.dependencies.len()))
+      .collect()
+  }
+
+  // when several tasks are available, the first one in alphabetical order is chosen
+  pub fn get_order(&self) -> Result<Vec<TaskId>, CycleError> {
+    self.check_acyclic()?;
+
+    let mut pending_dependencies = self.get_pending_dependencies_nums();
+    let mut available: BTreeSet<&TaskId> = pending_dependencies
+      .iter()
+      .filter(|(_, num)| **num == 0)
+      .map(|(id, _)| *id)
+      .collect();
+    let mut order: Vec<TaskId> = vec![];
+
+    while let Some(id) = available.pop_first() {
+      order.push(id.to_string());
+
+      for dependant in self.tasks[id].dependants.iter() {
+        let num = pending_dependencies.get_mut(dependant).unwrap();
+
+        *num -= 1;
+
+        if *num == 0 {
+          available.insert(dependant);
+        }
+      }
+    }
+
+    Ok(order)
+  }
+
+  // the free workers (lowest index first) take the available tasks in alphabetical order
+  pub fn schedule(&self, workers: usize) -> Result<Schedule, CycleError> {
+    assert!(workers > 0, "There must be at least one worker");
+
+    self.check_acyclic()?;
+
+    let mut pending_dependencies = self.get_pending_dependencies_nums();
+    let mut available: BTreeSet<&TaskId> = pending_dependencies
+      .iter()
+      .filter(|(_, num)| **num == 0)
+      .map(|(id, _)| *id)
+      .collect();
+    let mut free_workers: BTreeSet<usize> = (0..workers).collect();
+    let mut running: Vec<(Seconds, usize, &TaskId)> = vec![];
+    let mut scheduled_tasks: Vec<ScheduledTask> = vec![];
+    let mut time = 0;
+
+    loop {
+      while !free_workers.is_empty() && !available.is_empty() {
+        let worker = free_workers.pop_first().unwrap();
+        let id = available.pop_first().unwrap();
+        let end = time + self.tasks[id].duration;
+
+        running.push((end, worker, id));
+        scheduled_tasks.push(ScheduledTask {
+          id: id.to_string(),
+          worker,
+          start: time,
+          end,
+        });
+      }
+
+      time = match running.iter().map(|(end, _, _)| *end).min() {
+        Some(end) => end,
+        None => break,
+      };
+
+      let (finished, still_running): (Vec<_>, Vec<_>) =
+        running.into_iter().partition(|(end, _, _)| *end == time);
+
+      running = still_running;
+
+      for (_, worker, id) in finished {
+        free_workers.insert(worker);
+
+        for dependant in self.tasks[id].dependants.iter() {
+          let num = pending_dependencies.get_mut(dependant).unwrap();
+
+          *num -= 1;
+
+          if *num == 0 {
+            available.insert(dependant);
+          }
+        }
+      }
+    }
+
+    Ok(Schedule {
+      tasks: scheduled_tasks,
+      total_time: time,
+    })
+  }
+
+  // the longest chain of dependent tasks, which is the total time with unlimited workers
+  pub fn get_critical_path(&self) -> Result<CriticalPath, CycleError> {
+    let order = self.get_order()?;
+    let mut finish_times: HashMap<&TaskId, Seconds> = HashMap::new();
+    let mut previous_tasks: HashMap<&TaskId, &TaskId> = HashMap::new();
+    let mut last_task: Option<&TaskId> = None;
+
+    for id in order.iter() {
+      let task = &self.tasks[id];
+      let previous_task = task
+        .dependencies
+        .iter()
+        .max_by_key(|dependency| finish_times[dependency]);
+      let start = previous_task.map_or(0, |dependency| finish_times[dependency]);
+
+      if let Some(dependency) = previous_task {
+        previous_tasks.insert(id, dependency);
+      }
+
+      finish_times.insert(id, start + task.duration);
+
+      if last_task.is_none_or(|last| finish_times[last] < finish_times[id]) {
+        last_task = Some(id);
+      }
+    }
+
+    let mut tasks: Vec<TaskId> = vec![];
+    let mut current = last_task;
+
+    while let Some(id) = current {
+      tasks.push(id.to_string());
+      current = previous_tasks.get(id).cloned();
+    }
+
+    tasks.reverse();
+
+    Ok(CriticalPath {
+      total_time: last_task.map_or(0, |id| finish_times[id]),
+      tasks,
+    })
+  }
+}
+
+impl Schedule {
+  // one row per task with the worker, a bar of at most `max_columns` and the interval
+  pub fn to_gantt_string(&self, max_columns: usize) -> String {
+    let seconds_per_column = max(1, self.total_time.div_ceil(max_columns));
+    let columns = self.total_time.div_ceil(seconds_per_column);
+    let id_width = self
+      .tasks
+      .iter()
+      .map(|task| task.id.len())
+      .max()
+      .unwrap_or(0);
+    let mut lines: Vec<String> = vec![format!(
+      "total: {} seconds, {} second(s) per column",
+      self.total_time, seconds_per_column
+    )];
+    let mut tasks: Vec<&ScheduledTask> = self.tasks.iter().collect();
+
+    tasks.sort_by_key(|task| (task.start, task.worker));
+
+    for task in tasks {
+      let bar: String = (0..columns)
+        .map(|column| {
+          let column_start = column * seconds_per_column;
+
+          if task.start < column_start + seconds_per_column && task.end > column_start {
+            '#'
+          } else {
+            ' '
+          }
+        })
+        .collect();
+
+      lines.push(format!(
+        "{:<width$} w{} |{}| {}-{}",
+        task.id,
+        task.worker + 1,
+        bar,
+        task.start,
+        task.end,
+        width = id_width
+      ));
+    }
+
+    lines.join("\n")
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn get_example_graph() -> TaskGraph {
+    let mut graph = TaskGraph::new();
+
+    for (before, after) in &[
+      ("C", "A"),
+      ("C", "F"),
+      ("A", "B"),
+      ("A", "D"),
+      ("B", "E"),
+      ("D", "E"),
+      ("F", "E"),
+    ] {
+      graph.add_dependency(before, after);
+    }
+
+    for (idx, id) in ["A", "B", "C", "D", "E", "F"].iter().enumerate() {
+      graph.add_task(id, idx + 1);
+    }
+
+    graph
+  }
+
+  #[test]
+  fn test_get_order() {
+    assert_eq!(get_example_graph().get_order().unwrap().concat(), "CABDFE");
+  }
+
+  #[test]
+  fn test_schedule() {
+    let schedule = get_example_graph().schedule(2).unwrap();
+
+    assert_eq!(schedule.total_time, 15);
+    assert_eq!(
+      schedule.tasks[..3].to_vec(),
+      vec![
+        ScheduledTask {
+          id: "C".to_string(),
+          worker: 0,
+          start: 0,
+          end: 3,
+        },
+        ScheduledTask {
+          id: "A".to_string(),
+          worker: 0,
+          start: 3,
+          end: 4,
+        },
+        ScheduledTask {
+          id: "F".to_string(),
+          worker: 1,
+          start: 3,
+          end: 9,
+        },
+      ]
+    );
+  }
+
+  #[test]
+  fn test_get_critical_path() {
+    let graph = get_example_graph();
+    let critical_path = graph.get_critical_path().unwrap();
+
+    assert_eq!(
+      critical_path,
+      CriticalPath {
+        tasks: vec!["C".to_string(), "F".to_string(), "E".to_string()],
+        total_time: 14,
+      }
+    );
+    assert_eq!(graph.schedule(10).unwrap().total_time, 14);
+  }
+
+  #[test]
+  fn test_string_ids_and_cycles() {
+    let mut graph = TaskGraph::new();
+
+    graph.add_dependency("fetch", "build");
+    graph.add_dependency("build", "test");
+    graph.add_dependency("build", "package");
+    graph.add_task("build", 10);
+
+    assert_eq!(
+      graph.get_order().unwrap(),
+      vec!["fetch", "build", "package", "test"]
+    );
+
+    graph.add_dependency("test", "fetch");
+
+    let error = graph.schedule(2).unwrap_err();
+
+    assert_eq!(error.cycle, vec!["build", "test", "fetch", "build"]);
+    assert_eq!(
+      error.to_string(),
+      "the tasks have a cycle: build -> test -> fetch -> build"
+    );
+  }
+
+  #[test]
+  fn test_to_gantt_string() {
+    let schedule = get_example_graph().schedule(2).unwrap();
+
+    assert_eq!(
+      schedule.to_gantt_string(8),
+      [
+        "total: 15 seconds, 2 second(s) per column",
+        "C w1 |##      | 0-3",
+        "A w1 | #      | 3-4",
+        "F w2 | ####   | 3-9",
+        "B w1 |  #     | 4-6",
+        "D w1 |   ##   | 6-10",
+        "E w1 |     ###| 10-15",
+      ]
+      .join("\n")
+    );
+  }
+}
@@ -0,0 +1,327 @@
+use std::fmt;
+use std::mem;
+
+#[derive(Debug, PartialEq)]
+pub struct TreeNode {
+  pub children: Vec<TreeNode>,
+  pub metadata: Vec<usize>,
+}
+
+// the default drop is recursive, which overflows the stack for deep trees
+impl Drop for TreeNode {
+  fn drop(&mut self) {
+    let mut pending: Vec<TreeNode> = mem::take(&mut self.children);
+
+    while let Some(mut node) = pending.pop() {
+      pending.append(&mut node.children);
+    }
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum ParseErrorKind {
+  UnexpectedEnd,
+  TrailingNumbers,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub struct ParseError {
+  pub position: usize,
+  pub kind: ParseErrorKind,
+}
+
+impl fmt::Display for ParseError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let reason = match self.kind {
+      ParseErrorKind::UnexpectedEnd => "the numbers end in the middle of a node",
+      ParseErrorKind::TrailingNumbers => "unexpected numbers after the root node",
+    };
+
+    write!(f, "{} (position {})", reason, self.position)
+  }
+}
+
+// `enter` is called before the children of the node are visited and `leave` after them
+pub trait TreeVisitor {
+  fn enter(&mut self, _node: &TreeNode, _depth: usize) {}
+  fn leave(&mut self, _node: &TreeNode, _depth: usize) {}
+}
+
+struct PendingNode {
+  children_num: usize,
+  metadata_len: usize,
+  children: Vec<TreeNode>,
+}
+
+// Iterative version of: header, the children (recursively) and then the metadata
+pub fn parse_tree(nums: &[usize]) -> Result<TreeNode, ParseError> {
+  let unexpected_end = ParseError {
+    position: nums.len(),
+    kind: ParseErrorKind::UnexpectedEnd,
+  };
+  let mut pending_nodes: Vec<PendingNode> = vec![];
+  let mut position = 0;
+
+  loop {
+    let is_parent_complete = pending_nodes
+      .last()
+      .is_some_and(|node| node.children.len() == node.children_num);
+
+    if pending_nodes.is_empty() || !is_parent_complete {
+      if position + 2 > nums.len() {
+        return Err(unexpected_end);
+      }
+
+      pending_nodes.push(PendingNode {
+        children_num: nums[position],
+        metadata_len: nums[position + 1],
+        children: vec![],
+      });
+      position += 2;
+
+      continue;
+    }
+
+    let pending_node = pending_nodes.pop().unwrap();
+
+    if position + pending_node.metadata_len > nums.len() {
+      return Err(unexpected_end);
+    }
+
+    let node = TreeNode {
+      children: pending_node.children,
+      metadata: nums[position..position + pending_node.metadata_len].to_vec(),
+    };
+
+    position += pending_node.metadata_len;
+
+    match pending_nodes.last_mut() {
+      Some(parent) => parent.children.push(node),
+      None => {
+        if position != nums.len() {
+          return Err(ParseError {
+            position,
+            kind: ParseErrorKind::TrailingNumbers,
+          });
+        }
+
+        return Ok(node);
+      }
+    }
+  }
+}
+
+struct MetadataSumVisitor {
+  sum: usize,
+}
+
+impl TreeVisitor for MetadataSumVisitor {
+  fn enter(&mut self, node: &TreeNode, _depth: usize) {
+    self.sum += node.metadata.iter().sum::<usize>();
+  }
+}
+
+// the values of the visited nodes are stacked until their parent is left
+struct ValueVisitor {
+  values: Vec<usize>,
+}
+
+impl TreeVisitor for ValueVisitor {
+  fn leave(&mut self, node: &TreeNode, _depth: usize) {
+    let children_values = self
+      .values
+      .split_off(self.values.len() - node.children.len());
+    let value = if children_values.is_empty() {
+      node.metadata.iter().sum()
+    } else {
+      node
+        .metadata
+        .iter()
+        .filter_map(|idx| children_values.get(idx.wrapping_sub(1)))
+        .sum()
+    };
+
+    self.values.push(value);
+  }
+}
+
+struct SerializerVisitor {
+  nums: Vec<usize>,
+}
+
+impl TreeVisitor for SerializerVisitor {
+  fn enter(&mut self, node: &TreeNode, _depth: usize) {
+    self.nums.push(node.children.len());
+    self.nums.push(node.metadata.len());
+  }
+
+  fn leave(&mut self, node: &TreeNode, _depth: usize) {
+    self.nums.extend(node.metadata.iter());
+  }
+}
+
+struct PrettyPrinterVisitor {
+  lines: Vec<String>,
+}
+
+impl TreeVisitor for PrettyPrinterVisitor {
+  fn enter(&mut self, node: &TreeNode, depth: usize) {
+    self.lines.push(format!(
+      "{}- children: {}, metadata: {:?}",
+      "  ".repeat(depth),
+      node.children.len(),
+      node.metadata
+    ));
+  }
+}
+
+impl TreeNode {
+  // depth-first, with the children in order
+  pub fn walk<V: TreeVisitor>(&self, visitor: &mut V) {
+    let mut stack: Vec<(&TreeNode, usize, bool)> = vec![(self, 0, false)];
+
+    while let Some((node, depth, is_entered)) = stack.pop() {
+      if is_entered {
+        visitor.leave(node, depth);
+        continue;
+      }
+
+      visitor.enter(node, depth);
+      stack.push((node, depth, true));
+
+      for child in node.children.iter().rev() {
+        stack.push((child, depth + 1, false));
+      }
+    }
+  }
+
+  pub fn get_metadata_sum(&self) -> usize {
+    let mut visitor = MetadataSumVisitor { sum: 0 };
+
+    self.walk(&mut visitor);
+
+    visitor.sum
+  }
+
+  pub fn get_value(&self) -> usize {
+    let mut visitor = ValueVisitor { values: vec![] };
+
+    self.walk(&mut visitor);
+
+    visitor.values[0]
+  }
+
+  pub fn serialize(&self) -> Vec<usize> {
+    let mut visitor = SerializerVisitor { nums: vec![] };
+
+    self.walk(&mut visitor);
+
+    visitor.nums
+  }
+
+  pub fn to_pretty_string(&self) -> String {
+    let mut visitor = PrettyPrinterVisitor { lines: vec![] };
+
+    self.walk(&mut visitor);
+
+    visitor.lines.join("\n")
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn get_example_nums() -> Vec<usize> {
+    vec![2, 3, 0, 3, 10, 11, 12, 1, 1, 0, 1, 99, 2, 1, 1, 2]
+  }
+
+  // a chain of nodes with one child and one metadata entry, ending in a leaf
+  fn get_deep_nums(depth: usize) -> Vec<usize> {
+    let mut nums: Vec<usize> = vec![];
+
+    for _ in 0..depth {
+      nums.extend(&[1, 1]);
+    }
+
+    nums.extend(&[0, 1, 5]);
+    nums.extend(vec![1; depth]);
+
+    nums
+  }
+
+  #[test]
+  fn test_parse_tree() {
+    let tree = parse_tree(&get_example_nums()).unwrap();
+
+    assert_eq!(tree.metadata, vec![1, 1, 2]);
+    assert_eq!(tree.children.len(), 2);
+    assert_eq!(tree.children[0].metadata, vec![10, 11, 12]);
+    assert_eq!(tree.children[1].children[0].metadata, vec![99]);
+  }
+
+  #[test]
+  fn test_parse_tree_errors() {
+    assert_eq!(
+      parse_tree(&[1, 1, 0]),
+      Err(ParseError {
+        position: 3,
+        kind: ParseErrorKind::UnexpectedEnd,
+      })
+    );
+    assert_eq!(
+      parse_tree(&[0, 2, 1]),
+      Err(ParseError {
+        position: 3,
+        kind: ParseErrorKind::UnexpectedEnd,
+      })
+    );
+    assert_eq!(
+      parse_tree(&[0, 1, 1, 7]),
+      Err(ParseError {
+        position: 3,
+        kind: ParseErrorKind::TrailingNumbers,
+      })
+    );
+    assert_eq!(
+      parse_tree(&[0, 1, 1, 7]).unwrap_err().to_string(),
+      "unexpected numbers after the root node (position 3)"
+    );
+  }
+
+  #[test]
+  fn test_traversals() {
+    let tree = parse_tree(&get_example_nums()).unwrap();
+
+    assert_eq!(tree.get_metadata_sum(), 138);
+    assert_eq!(tree.get_value(), 66);
+    assert_eq!(tree.serialize(), get_example_nums());
+  }
+
+  #[test]
+  fn test_to_pretty_string() {
+    let tree = parse_tree(&get_example_nums()).unwrap();
+
+    assert_eq!(
+      tree.to_pretty_string(),
+      [
+        "- children: 2, metadata: [1, 1, 2]",
+        "  - children: 0, metadata: [10, 11, 12]",
+        "  - children: 1, metadata: [2]",
+        "    - children: 0, metadata: [99]",
+      ]
+      .join("\n")
+    );
+  }
+
+  #[test]
+  fn test_deep_tree() {
+    let depth = 500_000;
+    let nums = get_deep_nums(depth);
+    let tree = parse_tree(&nums).unwrap();
+
+    assert_eq!(tree.get_metadata_sum(), depth + 5);
+    assert_eq!(tree.get_value(), 5);
+    assert_eq!(tree.serialize(), nums);
+  }
+}
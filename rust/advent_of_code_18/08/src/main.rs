@@ -77,8 +77,11 @@ What is the value of the root node?
 
 */
 
+mod license_tree;
+
+use license_tree::{parse_tree, TreeNode};
 use regex::Regex;
-use std::collections::HashMap;
+use std::env;
 use std::fs::File;
 use std::io::prelude::*;
 
@@ -101,125 +104,47 @@ fn get_numbers() -> Vec<usize> {
   nums
 }
 
-type TreeNodeId = usize;
-
-#[derive(Debug)]
-struct TreeNode {
-  id: TreeNodeId,
-  metadata_nums_len: usize,
-  metadata_nums_sum: usize,
-  node_value: usize,
-  childs_num: usize,
-  childs_ids: Vec<TreeNodeId>,
-  is_root: bool,
-}
-
-type Tree = HashMap<TreeNodeId, TreeNode>;
-
-fn build_tree(nums: &mut Vec<usize>) -> Tree {
-  let mut nums = nums;
-  let mut tree: Tree = HashMap::new();
-
-  build_tree_rec(&mut nums, &mut tree, 0, true);
-
-  tree
+fn build_tree(nums: &[usize]) -> TreeNode {
+  parse_tree(nums).unwrap_or_else(|err| panic!("Invalid license: {}", err))
 }
 
-fn build_tree_rec(
-  nums: &mut Vec<usize>,
-  tree: &mut Tree,
-  id_count: usize,
-  is_root: bool,
-) -> (TreeNodeId, usize) {
-  let mut current_id = id_count;
-  let mut tree = tree;
-  let nums_len = nums.len();
-  let nums_used;
-
-  let mut current_tree_node = TreeNode {
-    id: 0,
-    metadata_nums_sum: 0,
-    metadata_nums_len: nums[1],
-    node_value: 0,
-    childs_num: nums[0],
-    childs_ids: vec![],
-    is_root,
-  };
-
-  if current_tree_node.childs_num == 0 {
-    nums_used = 2 + current_tree_node.metadata_nums_len;
-
-    for num in nums.iter().take(nums_used).skip(2) {
-      current_tree_node.metadata_nums_sum += num;
-    }
-
-    current_tree_node.node_value = current_tree_node.metadata_nums_sum;
-  } else {
-    let mut current_idx = 2;
-    for _ in 0..current_tree_node.childs_num {
-      let mut nums_sub_set = nums[current_idx..nums_len].to_vec();
-
-      let (child_id, child_used_nums) =
-        build_tree_rec(&mut nums_sub_set, &mut tree, current_id, false);
-
-      current_tree_node.childs_ids.push(child_id);
-
-      current_id = child_id;
-      current_idx += child_used_nums;
-    }
-
-    nums_used = current_idx + current_tree_node.metadata_nums_len;
-
-    for metadata_val in nums.iter().take(nums_used).skip(current_idx) {
-      current_tree_node.metadata_nums_sum += metadata_val;
-
-      if *metadata_val <= current_tree_node.childs_num {
-        let child_id = &current_tree_node.childs_ids[metadata_val - 1];
-        let child = tree.get(child_id).unwrap();
-
-        current_tree_node.node_value += child.node_value;
-      }
-    }
-  }
-
-  current_id += 1;
-  current_tree_node.id = current_id;
-
-  tree.insert(current_id, current_tree_node);
-
-  (current_id, nums_used)
+fn get_tree_first_check(tree: &TreeNode) -> usize {
+  tree.get_metadata_sum()
 }
 
-fn get_tree_first_check(tree: &mut Tree) -> usize {
-  let mut sum = 0;
-
-  for node in tree.values() {
-    sum += node.metadata_nums_sum;
-  }
-
-  sum
+fn get_tree_second_check(tree: &TreeNode) -> usize {
+  tree.get_value()
 }
 
-fn get_tree_second_check(tree: &mut Tree) -> usize {
-  for node in tree.values() {
-    if node.is_root {
-      return node.node_value;
-    }
-  }
+fn write_tree_file(tree: &TreeNode) {
+  let mut file = File::create("tree.txt").expect("Unable to create the file");
 
-  0
+  file
+    .write_all(tree.to_pretty_string().as_bytes())
+    .expect("Unable to write the file");
 }
 
 fn main() {
-  let mut numbers = get_numbers();
-  let mut tree = build_tree(&mut numbers);
+  let numbers = get_numbers();
+  let tree = build_tree(&numbers);
 
-  let first_check = get_tree_first_check(&mut tree);
-  let second_check = get_tree_second_check(&mut tree);
+  let first_check = get_tree_first_check(&tree);
+  let second_check = get_tree_second_check(&tree);
+  let args: Vec<String> = env::args().skip(1).collect();
 
   println!("Result:");
   println!("- (1) first total sum: {}", first_check);
   println!("- (2) second total sum: {}", second_check);
+  println!(
+    "- serialized tree matches input: {}",
+    tree.serialize() == numbers
+  );
+
+  // `cargo run -- tree`
+  if args.first().map(|x| x.as_ref()) == Some("tree") {
+    write_tree_file(&tree);
+    println!("- wrote the tree in tree.txt");
+  }
 }
 
 #[cfg(test)]
@@ -232,27 +157,27 @@ mod tests {
 
   #[test]
   fn test_get_tree_first_check() {
-    let mut nums = create_example_nums();
-    let mut tree = build_tree(&mut nums);
-    let first_check = get_tree_first_check(&mut tree);
+    let nums = create_example_nums();
+    let tree = build_tree(&nums);
+    let first_check = get_tree_first_check(&tree);
 
     assert_eq!(first_check, 138);
   }
 
   #[test]
   fn test_get_tree_first_check_2() {
-    let mut nums = vec![2, 3, 1, 3, 0, 1, 1, 10, 11, 12, 1, 1, 0, 1, 99, 2, 1, 1, 2];
-    let mut tree = build_tree(&mut nums);
-    let first_check = get_tree_first_check(&mut tree);
+    let nums = vec![2, 3, 1, 3, 0, 1, 1, 10, 11, 12, 1, 1, 0, 1, 99, 2, 1, 1, 2];
+    let tree = build_tree(&nums);
+    let first_check = get_tree_first_check(&tree);
 
     assert_eq!(first_check, 139);
   }
 
   #[test]
   fn test_get_tree_second_check() {
-    let mut nums = create_example_nums();
-    let mut tree = build_tree(&mut nums);
-    let second = get_tree_second_check(&mut tree);
+    let nums = create_example_nums();
+    let tree = build_tree(&nums);
+    let second = get_tree_second_check(&tree);
 
     assert_eq!(second, 66);
   }
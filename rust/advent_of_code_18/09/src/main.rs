@@ -79,7 +79,12 @@ What would the new winning Elf's score be if the number of the last marble were
 
 */
 
-use std::collections::VecDeque;
+mod marble_ring;
+
+use marble_ring::{Marble, MarbleRing};
+use std::env;
+use std::fs::File;
+use std::io::prelude::*;
 
 type PlayersNum = usize;
 type Score = usize;
@@ -88,133 +93,124 @@ type GameDescription = (PlayersNum, LastMarbleScore);
 
 const INPUT_TXT: GameDescription = (478, 71240);
 
-fn get_idx_to_substract(idx: usize, total: usize, to_remove: usize) -> usize {
-  let mut result = idx as i32 - to_remove as i32;
+#[derive(Debug, Clone)]
+struct ScoringRules {
+  scoring_multiple: Marble,
+  positions_back: usize,
+}
 
-  if result < 0 {
-    result += total as i32;
+impl Default for ScoringRules {
+  fn default() -> Self {
+    ScoringRules {
+      scoring_multiple: 23,
+      positions_back: 7,
+    }
   }
-
-  result as usize
 }
 
-// First personal implementation (not using a deque), time for 1st problem is ok (<5 seconds) but
-// not for 2nd (> 1 minute)
-fn get_high_score_for_game_description(game_description: GameDescription) -> Score {
-  let (players_num, last_marble_score) = game_description;
-  let mut players_scores: Vec<Score> = vec![];
-  let mut marbles = vec![0];
+#[derive(Debug, Clone, PartialEq)]
+struct ScoreEvent {
+  marble: Marble,
+  player_idx: usize,
+  removed_marble: Marble,
+  player_score: Score,
+}
 
-  let mut current_marble_idx = 0;
-  let mut current_player_idx = players_num - 1;
-  let mut current_marble_value = 0;
-  let mut marbles_since_last_score = 0;
+#[derive(Debug, Clone)]
+struct GameResult {
+  players_scores: Vec<Score>,
+  timeline: Vec<ScoreEvent>,
+}
 
-  for player_idx in 0..players_num {
-    players_scores.insert(player_idx, 0);
+impl GameResult {
+  fn get_high_score(&self) -> Score {
+    *self.players_scores.iter().max().unwrap()
   }
 
-  loop {
-    current_marble_value += 1;
-    marbles_since_last_score += 1;
-    current_player_idx = (current_player_idx + 1) % players_num;
-    let total_placed_marbles = marbles.len();
-
-    if marbles_since_last_score == 23 {
-      marbles_since_last_score = 0;
-
-      let other_marble_to_remove_idx =
-        get_idx_to_substract(current_marble_idx, total_placed_marbles, 7);
-
-      let removed_marble_value = marbles.remove(other_marble_to_remove_idx);
-
-      current_marble_idx = if other_marble_to_remove_idx == total_placed_marbles - 1 {
-        0
-      } else {
-        other_marble_to_remove_idx
-      };
-
-      let current_player_score = players_scores[current_player_idx];
-      let last_marble_worth = current_marble_value + removed_marble_value;
-
-      players_scores[current_player_idx] = current_player_score + last_marble_worth;
-    } else {
-      let next_marble_idx = match total_placed_marbles - current_marble_idx {
-        1 => 1,
-        2 => total_placed_marbles,
-        _ => current_marble_idx + 2,
-      };
-
-      marbles.insert(next_marble_idx, current_marble_value);
-
-      current_marble_idx = next_marble_idx;
-    }
-
-    if current_marble_value % 100_000 == 0 {
-      println!("current_marble_value {}", current_marble_value);
+  // the players are numbered from 1, as in the puzzle description
+  fn get_timeline_csv(&self) -> String {
+    let mut csv = "marble,player,removed_marble,points,player_score\n".to_string();
+
+    for event in &self.timeline {
+      csv.push_str(&format!(
+        "{},{},{},{},{}\n",
+        event.marble,
+        event.player_idx + 1,
+        event.removed_marble,
+        event.marble + event.removed_marble,
+        event.player_score
+      ));
     }
 
-    if current_marble_value >= last_marble_score {
-      break;
-    }
+    csv
   }
-
-  *players_scores.iter().max().unwrap()
 }
 
-// clockwise: positions > 0, counter-clockwise: positions < 0
-fn rotate_ring_start_index<T>(ring: &mut VecDeque<T>, positions: i32) {
-  if positions > 0 {
-    for _ in 0..positions {
-      let old_first = ring.pop_front().unwrap();
+// Each scoring marble is preceded by at least one placed marble (when the multiple is bigger than
+// one), so the ring never becomes empty
+fn play_game(game_description: GameDescription, rules: &ScoringRules) -> GameResult {
+  assert!(
+    rules.scoring_multiple > 1,
+    "The scoring multiple must be bigger than one"
+  );
 
-      ring.push_back(old_first);
-    }
-  } else if positions < 0 {
-    for _ in positions..0 {
-      let old_last = ring.pop_back().unwrap();
+  let (players_num, marbles_num) = game_description;
+  let mut players_scores = vec![0; players_num];
+  let mut timeline: Vec<ScoreEvent> = vec![];
+  let mut ring = MarbleRing::new(0);
 
-      ring.push_front(old_last);
+  for current_marble in 1..=marbles_num {
+    if current_marble % rules.scoring_multiple == 0 {
+      ring.move_counter_clockwise(rules.positions_back);
+
+      let player_idx = (current_marble - 1) % players_num;
+      let removed_marble = ring.remove_current().unwrap();
+
+      players_scores[player_idx] += current_marble + removed_marble;
+      timeline.push(ScoreEvent {
+        marble: current_marble,
+        player_idx,
+        removed_marble,
+        player_score: players_scores[player_idx],
+      });
+    } else {
+      ring.move_clockwise(1);
+      ring.insert_after_current(current_marble);
     }
   }
+
+  GameResult {
+    players_scores,
+    timeline,
+  }
 }
 
-// Searched for an optimized version for problem 2, updated it afterwards:
-// (note in original version the first for-loop was wrong)
-// original: https://www.reddit.com/r/adventofcode/comments/a4i97s/2018_day_9_solutions/ebfp2zp
 fn calculate_high_score(game_description: GameDescription) -> Score {
-  let (players_num, marbles_num) = game_description;
-  let mut players_score = vec![0; players_num];
-  let mut ring = VecDeque::new();
-
-  ring.push_front(0);
-
-  for current_marble in 1..=marbles_num {
-    if current_marble % 23 == 0 {
-      rotate_ring_start_index(&mut ring, -7);
-
-      let player_idx = current_marble % players_num;
-      let removed_marble = ring.pop_front().unwrap();
-      let new_extra_score = current_marble + removed_marble;
-
-      players_score[player_idx] += new_extra_score;
-    } else {
-      rotate_ring_start_index(&mut ring, 2);
+  play_game(game_description, &ScoringRules::default()).get_high_score()
+}
 
-      ring.push_front(current_marble);
-    }
-  }
+fn write_timeline_file(game_result: &GameResult) {
+  let mut file = File::create("timeline.csv").expect("Unable to create the file");
 
-  *players_score.iter().max().unwrap()
+  file
+    .write_all(game_result.get_timeline_csv().as_bytes())
+    .expect("Unable to write the file");
 }
 
 fn main() {
-  let first_result = get_high_score_for_game_description(INPUT_TXT);
+  let first_game = play_game(INPUT_TXT, &ScoringRules::default());
   let second_result = calculate_high_score((INPUT_TXT.0, INPUT_TXT.1 * 100));
+  let args: Vec<String> = env::args().skip(1).collect();
 
   println!("Results:");
-  println!("- (1) first high score: {}", first_result);
+  println!("- (1) first high score: {}", first_game.get_high_score());
   println!("- (2) second high score: {}", second_result);
+
+  // `cargo run --release -- timeline`
+  if args.first().map(|x| x.as_ref()) == Some("timeline") {
+    write_timeline_file(&first_game);
+    println!("- wrote the score timeline in timeline.csv");
+  }
 }
 
 #[cfg(test)]
@@ -233,27 +229,51 @@ mod tests {
   }
 
   #[test]
-  fn test_get_idx_to_substract() {
-    assert_eq!(get_idx_to_substract(0, 1, 0), 0);
-    assert_eq!(get_idx_to_substract(9, 10, 7), 2);
-    assert_eq!(get_idx_to_substract(0, 2, 1), 1);
-  }
-
-  #[test]
-  fn test_get_high_score_for_game_description() {
+  fn test_calculate_high_score() {
     for (game_description, high_score) in get_examples() {
-      let result = get_high_score_for_game_description(game_description);
+      let result = calculate_high_score(game_description);
 
       assert_eq!(result, high_score);
     }
   }
 
   #[test]
-  fn test_calculate_high_score() {
-    for (game_description, high_score) in get_examples() {
-      let result = calculate_high_score(game_description);
+  fn test_play_game_timeline() {
+    let game_result = play_game((9, 25), &ScoringRules::default());
+
+    assert_eq!(
+      game_result.timeline,
+      vec![ScoreEvent {
+        marble: 23,
+        player_idx: 4,
+        removed_marble: 9,
+        player_score: 32,
+      }]
+    );
+    assert_eq!(
+      game_result.get_timeline_csv(),
+      "marble,player,removed_marble,points,player_score\n23,5,9,32,32\n"
+    );
+  }
 
-      assert_eq!(result, high_score);
-    }
+  #[test]
+  fn test_play_game_custom_rules() {
+    let rules = ScoringRules {
+      scoring_multiple: 5,
+      positions_back: 2,
+    };
+    let game_result = play_game((2, 10), &rules);
+
+    // 0 (4) 2 1 3: the marble 5 removes the marble 3, two positions counter-clockwise of 4
+    // 0 (9) 4 6 2 7 1 8: the marble 10 removes the marble 8
+    assert_eq!(
+      game_result
+        .timeline
+        .iter()
+        .map(|event| (event.marble, event.player_idx, event.removed_marble))
+        .collect::<Vec<(Marble, usize, Marble)>>(),
+      vec![(5, 0, 3), (10, 1, 8)]
+    );
+    assert_eq!(game_result.players_scores, vec![8, 18]);
   }
 }
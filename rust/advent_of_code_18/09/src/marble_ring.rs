@@ -0,0 +1,149 @@
+pub type Marble = usize;
+
+#[derive(Debug, Clone)]
+struct Node {
+  marble: Marble,
+  prev: usize,
+  next: usize,
+}
+
+// Circular doubly linked list whose nodes live in a vector (the arena) and are linked by index.
+// Removed nodes are reused by the next insertions
+#[derive(Debug, Clone)]
+pub struct MarbleRing {
+  nodes: Vec<Node>,
+  free_nodes: Vec<usize>,
+  current: usize,
+  len: usize,
+}
+
+impl MarbleRing {
+  pub fn new(first_marble: Marble) -> MarbleRing {
+    MarbleRing {
+      nodes: vec![Node {
+        marble: first_marble,
+        prev: 0,
+        next: 0,
+      }],
+      free_nodes: vec![],
+      current: 0,
+      len: 1,
+    }
+  }
+
+  pub fn move_clockwise(&mut self, positions: usize) {
+    for _ in 0..(positions % self.len) {
+      self.current = self.nodes[self.current].next;
+    }
+  }
+
+  pub fn move_counter_clockwise(&mut self, positions: usize) {
+    for _ in 0..(positions % self.len) {
+      self.current = self.nodes[self.current].prev;
+    }
+  }
+
+  // the new marble is placed clockwise of the current one and becomes the current one
+  pub fn insert_after_current(&mut self, marble: Marble) {
+    let prev = self.current;
+    let next = self.nodes[prev].next;
+    let node = Node { marble, prev, next };
+    let idx = match self.free_nodes.pop() {
+      Some(idx) => {
+        self.nodes[idx] = node;
+        idx
+      }
+      None => {
+        self.nodes.push(node);
+        self.nodes.len() - 1
+      }
+    };
+
+    self.nodes[prev].next = idx;
+    self.nodes[next].prev = idx;
+    self.current = idx;
+    self.len += 1;
+  }
+
+  // the marble clockwise of the removed one becomes the current one. The last marble can't be
+  // removed, so it returns None in that case
+  pub fn remove_current(&mut self) -> Option<Marble> {
+    if self.len == 1 {
+      return None;
+    }
+
+    let idx = self.current;
+    let Node { marble, prev, next } = self.nodes[idx].clone();
+
+    self.nodes[prev].next = next;
+    self.nodes[next].prev = prev;
+    self.free_nodes.push(idx);
+    self.current = next;
+    self.len -= 1;
+
+    Some(marble)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  // the marbles in clockwise order starting from the current one
+  fn get_marbles(ring: &MarbleRing) -> Vec<Marble> {
+    let mut marbles = vec![];
+    let mut idx = ring.current;
+
+    for _ in 0..ring.len {
+      marbles.push(ring.nodes[idx].marble);
+      idx = ring.nodes[idx].next;
+    }
+
+    marbles
+  }
+
+  #[test]
+  fn test_insert_and_move() {
+    let mut ring = MarbleRing::new(0);
+
+    for marble in 1..=4 {
+      ring.move_clockwise(1);
+      ring.insert_after_current(marble);
+    }
+
+    // 0 4 2 1 3, with 4 as the current marble
+    assert_eq!(get_marbles(&ring), vec![4, 2, 1, 3, 0]);
+    assert_eq!(ring.len, 5);
+
+    ring.move_counter_clockwise(7);
+
+    assert_eq!(ring.nodes[ring.current].marble, 3);
+
+    ring.move_clockwise(6);
+
+    assert_eq!(ring.nodes[ring.current].marble, 0);
+  }
+
+  #[test]
+  fn test_remove_current() {
+    let mut ring = MarbleRing::new(0);
+
+    ring.insert_after_current(1);
+    ring.insert_after_current(2);
+
+    assert_eq!(ring.remove_current(), Some(2));
+    assert_eq!(ring.nodes[ring.current].marble, 0);
+    assert_eq!(get_marbles(&ring), vec![0, 1]);
+
+    // the freed node is reused
+    ring.insert_after_current(3);
+
+    assert_eq!(get_marbles(&ring), vec![3, 1, 0]);
+    assert_eq!(ring.nodes.len(), 3);
+
+    assert_eq!(ring.remove_current(), Some(3));
+    assert_eq!(ring.remove_current(), Some(1));
+    assert_eq!(ring.remove_current(), None);
+    assert_eq!(get_marbles(&ring), vec![0]);
+  }
+}
@@ -0,0 +1,295 @@
+use std::fs::File;
+use std::io::prelude::*;
+use std::iter::FromIterator;
+use std::thread;
+use std::time::Duration;
+
+use crate::Point;
+
+#[derive(Debug, PartialEq)]
+pub struct AnimationOptions {
+  pub seconds_before: usize,
+  pub seconds_after: usize,
+  pub step: usize,
+  pub width: usize,
+  pub height: usize,
+  pub ppm_dir: Option<String>,
+}
+
+impl Default for AnimationOptions {
+  fn default() -> Self {
+    AnimationOptions {
+      seconds_before: 10,
+      seconds_after: 3,
+      step: 1,
+      width: 80,
+      height: 24,
+      ppm_dir: None,
+    }
+  }
+}
+
+impl AnimationOptions {
+  // e.g. `--before 100 --after 5 --step 5 --width 120 --height 40 --ppm frames`
+  pub fn parse_args(args: &[String]) -> Result<AnimationOptions, String> {
+    let mut options = AnimationOptions::default();
+    let mut args_iter = args.iter();
+
+    while let Some(flag) = args_iter.next() {
+      let value = args_iter
+        .next()
+        .ok_or(format!("Missing value for {}", flag))?;
+
+      if flag == "--ppm" {
+        options.ppm_dir = Some(value.to_string());
+        continue;
+      }
+
+      let number = value
+        .parse::<usize>()
+        .map_err(|_| format!("Invalid number for {}: {}", flag, value))?;
+
+      match flag.as_ref() {
+        "--before" => options.seconds_before = number,
+        "--after" => options.seconds_after = number,
+        "--step" => options.step = number,
+        "--width" => options.width = number,
+        "--height" => options.height = number,
+        _ => return Err(format!("Unknown option: {}", flag)),
+      }
+    }
+
+    if options.step == 0 || options.width < 2 || options.height < 2 {
+      return Err("The step must be positive and the size at least 2x2".to_string());
+    }
+
+    Ok(options)
+  }
+
+  // the steps go from the center in both directions, so its frame is always shown
+  pub fn get_frame_seconds(&self, center_second: usize) -> Vec<usize> {
+    let seconds_before = self.seconds_before.min(center_second);
+    let mut frame_seconds: Vec<usize> = (self.step..=seconds_before)
+      .step_by(self.step)
+      .map(|offset| center_second - offset)
+      .collect();
+
+    frame_seconds.reverse();
+    frame_seconds.extend((center_second..=center_second + self.seconds_after).step_by(self.step));
+
+    frame_seconds
+  }
+}
+
+// the positions are scaled down (never up) with the same factor in both axes, so the whole
+// boundary fits in `width` x `height` cells
+pub fn get_scaled_grid(points: &[Point], width: usize, height: usize) -> Vec<Vec<bool>> {
+  let boundary = Point::calculate_boundary_of_points(points);
+  let scale = [
+    1.0,
+    (boundary.max_x - boundary.min_x) / (width - 1) as f64,
+    (boundary.max_y - boundary.min_y) / (height - 1) as f64,
+  ]
+  .iter()
+  .cloned()
+  .fold(0.0, f64::max);
+  let mut grid: Vec<Vec<bool>> = vec![vec![false; width]; height];
+
+  for point in points {
+    let x = ((point.position.x - boundary.min_x) / scale).round() as usize;
+    let y = ((point.position.y - boundary.min_y) / scale).round() as usize;
+
+    grid[y.min(height - 1)][x.min(width - 1)] = true;
+  }
+
+  grid
+}
+
+pub fn render_terminal_frame(points: &[Point], width: usize, height: usize) -> String {
+  get_scaled_grid(points, width, height)
+    .iter()
+    .map(|row| String::from_iter(row.iter().map(|lit| if *lit { '#' } else { '.' })))
+    .collect::<Vec<String>>()
+    .join("\n")
+}
+
+pub fn render_ppm_frame(points: &[Point], width: usize, height: usize) -> Vec<u8> {
+  let mut bytes: Vec<u8> = format!("P6\n{} {}\n255\n", width, height).into_bytes();
+
+  for row in get_scaled_grid(points, width, height) {
+    for lit in row {
+      let color: [u8; 3] = if lit { [255, 215, 0] } else { [10, 10, 40] };
+
+      bytes.extend_from_slice(&color);
+    }
+  }
+
+  bytes
+}
+
+// calls `render_frame` for each second of the animation, moving the points between frames
+fn for_each_frame<F>(
+  points: &[Point],
+  center_second: usize,
+  options: &AnimationOptions,
+  mut render_frame: F,
+) where
+  F: FnMut(usize, &[Point]),
+{
+  let frame_seconds = options.get_frame_seconds(center_second);
+  let mut points: Vec<Point> = points.to_vec();
+  let mut current_second = 0;
+
+  for second in frame_seconds {
+    for point in points.iter_mut() {
+      point.move_time_n_seconds_for_point(second - current_second);
+    }
+
+    current_second = second;
+    render_frame(second, &points);
+  }
+}
+
+pub fn play_in_terminal(points: &[Point], center_second: usize, options: &AnimationOptions) {
+  for_each_frame(points, center_second, options, |second, frame_points| {
+    // clears the screen and moves the cursor to the top left corner
+    print!("\x1b[2J\x1b[H");
+    println!("second: {}", second);
+    println!(
+      "{}",
+      render_terminal_frame(frame_points, options.width, options.height)
+    );
+
+    thread::sleep(Duration::from_millis(150));
+  });
+}
+
+pub fn write_ppm_frames(
+  points: &[Point],
+  center_second: usize,
+  options: &AnimationOptions,
+  dir: &str,
+) {
+  std::fs::create_dir_all(dir).expect("Unable to create the frames directory");
+
+  for_each_frame(points, center_second, options, |second, frame_points| {
+    let path = format!("{}/second_{:06}.ppm", dir, second);
+    let mut file = File::create(&path).expect("Unable to create the frame file");
+
+    file
+      .write_all(&render_ppm_frame(
+        frame_points,
+        options.width,
+        options.height,
+      ))
+      .expect("Unable to write the frame file");
+  });
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{Coord, Velocity};
+
+  fn get_points(positions: &[(f64, f64)]) -> Vec<Point> {
+    positions
+      .iter()
+      .map(|(x, y)| Point {
+        position: Coord { x: *x, y: *y },
+        velocity: Velocity { x: 1.0, y: -1.0 },
+      })
+      .collect()
+  }
+
+  #[test]
+  fn test_parse_args() {
+    let args: Vec<String> = ["--before", "20", "--step", "2", "--ppm", "frames"]
+      .iter()
+      .map(|x| x.to_string())
+      .collect();
+
+    assert_eq!(
+      AnimationOptions::parse_args(&args),
+      Ok(AnimationOptions {
+        seconds_before: 20,
+        step: 2,
+        ppm_dir: Some("frames".to_string()),
+        ..AnimationOptions::default()
+      })
+    );
+    assert!(AnimationOptions::parse_args(&["--step".to_string()]).is_err());
+    assert!(AnimationOptions::parse_args(&["--step".to_string(), "0".to_string()]).is_err());
+    assert!(AnimationOptions::parse_args(&["--speed".to_string(), "1".to_string()]).is_err());
+  }
+
+  #[test]
+  fn test_get_frame_seconds() {
+    let options = AnimationOptions {
+      seconds_before: 4,
+      seconds_after: 2,
+      step: 2,
+      ..AnimationOptions::default()
+    };
+
+    assert_eq!(options.get_frame_seconds(10), vec![6, 8, 10, 12]);
+    assert_eq!(options.get_frame_seconds(1), vec![1, 3]);
+    assert_eq!(options.get_frame_seconds(5), vec![1, 3, 5, 7]);
+
+    let options = AnimationOptions {
+      seconds_before: 5,
+      seconds_after: 5,
+      step: 3,
+      ..AnimationOptions::default()
+    };
+
+    assert_eq!(options.get_frame_seconds(7), vec![4, 7, 10]);
+  }
+
+  #[test]
+  fn test_render_terminal_frame() {
+    let points = get_points(&[(0.0, 0.0), (2.0, 1.0)]);
+
+    assert_eq!(render_terminal_frame(&points, 4, 2), "#...\n..#.");
+
+    let points = get_points(&[(0.0, 0.0), (100.0, 50.0), (50.0, 0.0)]);
+
+    assert_eq!(render_terminal_frame(&points, 5, 3), "#.#..\n.....\n....#");
+  }
+
+  #[test]
+  fn test_render_ppm_frame() {
+    let points = get_points(&[(0.0, 0.0), (1.0, 1.0)]);
+    let ppm = render_ppm_frame(&points, 2, 2);
+    let header = "P6\n2 2\n255\n";
+
+    assert_eq!(&ppm[..header.len()], header.as_bytes());
+    assert_eq!(
+      &ppm[header.len()..],
+      &[255, 215, 0, 10, 10, 40, 10, 10, 40, 255, 215, 0]
+    );
+  }
+
+  #[test]
+  fn test_for_each_frame() {
+    let points = get_points(&[(0.0, 10.0)]);
+    let options = AnimationOptions {
+      seconds_before: 2,
+      seconds_after: 1,
+      ..AnimationOptions::default()
+    };
+    let mut positions: Vec<(usize, f64, f64)> = vec![];
+
+    for_each_frame(&points, 5, &options, |second, frame_points| {
+      positions.push((
+        second,
+        frame_points[0].position.x,
+        frame_points[0].position.y,
+      ));
+    });
+
+    assert_eq!(
+      positions,
+      vec![(3, 3.0, 7.0), (4, 4.0, 6.0), (5, 5.0, 5.0), (6, 6.0, 4.0)]
+    );
+  }
+}
@@ -0,0 +1,204 @@
+use std::collections::HashSet;
+
+pub const GLYPH_WIDTH: usize = 6;
+pub const GLYPH_HEIGHT: usize = 10;
+
+// the letters known to appear in the messages of the puzzle, each with 10 rows of 6 columns
+const GLYPHS: [(char, [&str; GLYPH_HEIGHT]); 15] = [
+  (
+    'A',
+    [
+      "..##..", ".#..#.", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#",
+      "#....#",
+    ],
+  ),
+  (
+    'B',
+    [
+      "#####.", "#....#", "#....#", "#....#", "#####.", "#....#", "#....#", "#....#", "#....#",
+      "#####.",
+    ],
+  ),
+  (
+    'C',
+    [
+      ".####.", "#....#", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#....#",
+      ".####.",
+    ],
+  ),
+  (
+    'E',
+    [
+      "######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....",
+      "######",
+    ],
+  ),
+  (
+    'F',
+    [
+      "######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....",
+      "#.....",
+    ],
+  ),
+  (
+    'G',
+    [
+      ".####.", "#....#", "#.....", "#.....", "#.....", "#..###", "#....#", "#....#", "#...##",
+      ".###.#",
+    ],
+  ),
+  (
+    'H',
+    [
+      "#....#", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#",
+      "#....#",
+    ],
+  ),
+  (
+    'J',
+    [
+      "...###", "....#.", "....#.", "....#.", "....#.", "....#.", "....#.", "#...#.", "#...#.",
+      ".###..",
+    ],
+  ),
+  (
+    'K',
+    [
+      "#....#", "#...#.", "#..#..", "#.#...", "##....", "##....", "#.#...", "#..#..", "#...#.",
+      "#....#",
+    ],
+  ),
+  (
+    'L',
+    [
+      "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....",
+      "######",
+    ],
+  ),
+  (
+    'N',
+    [
+      "#....#", "##...#", "##...#", "#.#..#", "#.#..#", "#..#.#", "#..#.#", "#...##", "#...##",
+      "#....#",
+    ],
+  ),
+  (
+    'P',
+    [
+      "#####.", "#....#", "#....#", "#....#", "#####.", "#.....", "#.....", "#.....", "#.....",
+      "#.....",
+    ],
+  ),
+  (
+    'R',
+    [
+      "#####.", "#....#", "#....#", "#....#", "#####.", "#..#..", "#...#.", "#...#.", "#....#",
+      "#....#",
+    ],
+  ),
+  (
+    'X',
+    [
+      "#....#", "#....#", ".#..#.", ".#..#.", "..##..", "..##..", ".#..#.", ".#..#.", "#....#",
+      "#....#",
+    ],
+  ),
+  (
+    'Z',
+    [
+      "######", ".....#", ".....#", "....#.", "...#..", "..#...", ".#....", "#.....", "#.....",
+      "######",
+    ],
+  ),
+];
+
+fn recognise_glyph(lit: &HashSet<(usize, usize)>, start_x: usize) -> char {
+  for (letter, rows) in GLYPHS.iter() {
+    let matches = rows.iter().enumerate().all(|(y, row)| {
+      row
+        .chars()
+        .enumerate()
+        .all(|(x, ch)| (ch == '#') == lit.contains(&(start_x + x, y)))
+    });
+
+    if matches {
+      return *letter;
+    }
+  }
+
+  '?'
+}
+
+// `lit` has the lit positions relative to the top left corner of the message. The letters are
+// separated by empty columns and unknown ones are returned as '?'
+pub fn recognise_message(lit: &HashSet<(usize, usize)>) -> Option<String> {
+  let width = lit.iter().map(|(x, _)| x + 1).max()?;
+  let height = lit.iter().map(|(_, y)| y + 1).max()?;
+
+  if height != GLYPH_HEIGHT {
+    return None;
+  }
+
+  let is_column_empty = |x: usize| (0..height).all(|y| !lit.contains(&(x, y)));
+  let mut message = String::new();
+  let mut x = 0;
+
+  while x < width {
+    if is_column_empty(x) {
+      x += 1;
+      continue;
+    }
+
+    message.push(recognise_glyph(lit, x));
+    x += GLYPH_WIDTH;
+  }
+
+  Some(message)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn render_letters(letters: &str) -> HashSet<(usize, usize)> {
+    let mut lit: HashSet<(usize, usize)> = HashSet::new();
+
+    for (idx, letter) in letters.chars().enumerate() {
+      let (_, rows) = GLYPHS.iter().find(|(ch, _)| *ch == letter).unwrap();
+
+      for (y, row) in rows.iter().enumerate() {
+        for (x, ch) in row.chars().enumerate() {
+          if ch == '#' {
+            lit.insert((idx * (GLYPH_WIDTH + 2) + x, y));
+          }
+        }
+      }
+    }
+
+    lit
+  }
+
+  #[test]
+  fn test_recognise_message() {
+    let letters: String = GLYPHS.iter().map(|(ch, _)| *ch).collect();
+
+    assert_eq!(
+      recognise_message(&render_letters(&letters)),
+      Some(letters.clone())
+    );
+    assert_eq!(
+      recognise_message(&render_letters("ZEH")),
+      Some("ZEH".to_string())
+    );
+  }
+
+  #[test]
+  fn test_recognise_message_unknown() {
+    let mut lit = render_letters("AB");
+
+    lit.remove(&(2, 0));
+
+    assert_eq!(recognise_message(&lit), Some("?B".to_string()));
+    assert_eq!(recognise_message(&HashSet::new()), None);
+  }
+}
@@ -161,7 +161,13 @@ What message will eventually appear in the sky?
 
 */
 
+mod animation;
+mod font;
+
+use animation::AnimationOptions;
 use regex::Regex;
+use std::collections::HashSet;
+use std::env;
 use std::fs::File;
 use std::io::prelude::*;
 use std::path::Path;
@@ -218,39 +224,46 @@ impl Point {
     points
   }
 
-  fn calculate_second_of_min_boundary(
-    points: &mut Vec<Point>,
-    seconds_beginning: usize,
-    seconds_end: usize,
-  ) -> usize {
-    let points = points.clone();
-    let mut min_boundary_dimensions: Option<LengthUnit> = None;
-    let mut chosen_second = 0;
-    let mut points = points;
+  fn calculate_boundary_dimensions_at_second(points: &[Point], second: usize) -> LengthUnit {
+    let mut points: Vec<Point> = points.to_vec();
 
     points.iter_mut().for_each(|point| {
-      point.move_time_n_seconds_for_point(seconds_beginning);
+      point.move_time_n_seconds_for_point(second);
     });
 
-    for second in seconds_beginning..seconds_end {
-      let mut boundary = Point::calculate_boundary_of_points(&mut points);
-      let boundary_dimensions = Point::calculate_boundary_dimensions(&mut boundary);
+    let mut boundary = Point::calculate_boundary_of_points(&points);
 
-      if min_boundary_dimensions.is_none() || boundary_dimensions < min_boundary_dimensions.unwrap()
-      {
-        min_boundary_dimensions = Some(boundary_dimensions);
-        chosen_second = second;
-      }
+    Point::calculate_boundary_dimensions(&mut boundary)
+  }
+
+  // The dimensions of the boundary (a sum of maximums minus minimums of linear functions of the
+  // time) are convex in the time, so the minimum is found with a binary search of the slope
+  fn calculate_second_of_min_boundary(points: &[Point]) -> usize {
+    let dimensions_at =
+      |second: usize| Point::calculate_boundary_dimensions_at_second(points, second);
+    let is_decreasing_at = |second: usize| dimensions_at(second + 1) < dimensions_at(second);
+    let mut seconds_beginning = 0;
+    let mut seconds_end = 1;
+
+    while is_decreasing_at(seconds_end) {
+      seconds_beginning = seconds_end;
+      seconds_end *= 2;
+    }
+
+    while seconds_beginning < seconds_end {
+      let middle = (seconds_beginning + seconds_end) / 2;
 
-      points.iter_mut().for_each(|point| {
-        point.move_time_n_seconds_for_point(1);
-      });
+      if is_decreasing_at(middle) {
+        seconds_beginning = middle + 1;
+      } else {
+        seconds_end = middle;
+      }
     }
 
-    chosen_second
+    seconds_beginning
   }
 
-  fn calculate_boundary_of_points(points: &mut Vec<Point>) -> Boundary {
+  fn calculate_boundary_of_points(points: &[Point]) -> Boundary {
     let mut boundary = Boundary {
       min_x: points[0].position.x,
       max_x: points[0].position.x,
@@ -258,7 +271,7 @@ impl Point {
       max_y: points[0].position.y,
     };
 
-    points.iter_mut().for_each(|point| {
+    points.iter().for_each(|point| {
       if point.position.x < boundary.min_x {
         boundary.min_x = point.position.x;
       }
@@ -285,24 +298,48 @@ impl Point {
     self.position.y += self.velocity.y * (seconds as SpeedUnit);
   }
 
-  fn write_file_for_seconds(points: &mut Vec<Point>, second: usize) {
+  fn get_lit_positions_for_seconds(points: &[Point], second: usize) -> HashSet<(usize, usize)> {
+    let mut points: Vec<Point> = points.to_vec();
+
+    for point in points.iter_mut() {
+      point.move_time_n_seconds_for_point(second);
+    }
+
+    let boundary = Point::calculate_boundary_of_points(&points);
+
+    points
+      .iter()
+      .map(|point| {
+        (
+          (point.position.x - boundary.min_x) as usize,
+          (point.position.y - boundary.min_y) as usize,
+        )
+      })
+      .collect()
+  }
+
+  fn read_message_for_seconds(points: &[Point], second: usize) -> Option<String> {
+    font::recognise_message(&Point::get_lit_positions_for_seconds(points, second))
+  }
+
+  fn write_file_for_seconds(points: &[Point], second: usize) {
     use std::iter::FromIterator;
 
-    let mut points = points.clone();
+    let mut points = points.to_vec();
 
     for point in points.iter_mut() {
       point.move_time_n_seconds_for_point(second);
     }
 
-    let boundary = Point::calculate_boundary_of_points(&mut points);
+    let boundary = Point::calculate_boundary_of_points(&points);
     let mut contents: Vec<Vec<char>> = vec![];
 
     for _ in 0..=(boundary.max_y as i32 - boundary.min_y as i32) {
-      let mut line = vec![];
-      for _ in 0..=(boundary.max_x as i32 - boundary.min_x as i32) {
-        line.push('_');
-      }
-      contents.push(line);
+      contents.push(vec![
+        '_';
+        (boundary.max_x as i32 - boundary.min_x as i32 + 1)
+          as usize
+      ]);
     }
 
     for point in points.iter_mut() {
@@ -316,7 +353,7 @@ impl Point {
 
     let path = Path::new("result.txt");
 
-    let mut file = File::create(&path).unwrap();
+    let mut file = File::create(path).unwrap();
     file
       .write_all(
         contents
@@ -337,20 +374,42 @@ fn get_input_points() -> Vec<Point> {
     .read_to_string(&mut contents)
     .expect("Unable to read the file");
 
-  let mut descriptions: Vec<&str> = contents.lines().map(|x| x).collect();
+  let mut descriptions: Vec<&str> = contents.lines().collect();
 
   Point::create_from_descriptions(&mut descriptions)
 }
 
 fn main() {
-  let mut input_points = get_input_points();
+  let input_points = get_input_points();
+
+  let second = Point::calculate_second_of_min_boundary(&input_points);
+  let args: Vec<String> = env::args().skip(1).collect();
+
+  // `cargo run -- animate [--before N] [--after N] [--step N] [--width N] [--height N] [--ppm DIR]`
+  if args.first().map(|x| x.as_ref()) == Some("animate") {
+    let options = AnimationOptions::parse_args(&args[1..]).unwrap_or_else(|err| {
+      eprintln!("{}", err);
+      std::process::exit(1);
+    });
+
+    match options.ppm_dir {
+      Some(ref dir) => animation::write_ppm_frames(&input_points, second, &options, dir),
+      None => animation::play_in_terminal(&input_points, second, &options),
+    }
+
+    return;
+  }
 
-  let second = Point::calculate_second_of_min_boundary(&mut input_points, 0, 20000);
+  let message = Point::read_message_for_seconds(&input_points, second);
 
-  Point::write_file_for_seconds(&mut input_points, second);
+  Point::write_file_for_seconds(&input_points, second);
 
   println!("Results");
-  println!("- (1) wrote file with second: {}", second);
+  println!(
+    "- (1) message: {}",
+    message.unwrap_or_else(|| "(unknown, see result.txt)".to_string())
+  );
+  println!("- (2) second: {}", second);
 }
 
 #[cfg(test)]
@@ -397,9 +456,37 @@ mod tests {
 
   #[test]
   fn test_calculate_second_of_min_boundary() {
-    let mut points = get_example_points();
-    let second = Point::calculate_second_of_min_boundary(&mut points, 0, 10);
+    let points = get_example_points();
+    let second = Point::calculate_second_of_min_boundary(&points);
 
     assert_eq!(second, 3);
   }
+
+  #[test]
+  fn test_calculate_second_of_min_boundary_far() {
+    let points = get_example_points()
+      .iter()
+      .map(|point| Point {
+        position: Coord {
+          x: point.position.x - point.velocity.x * 10_000.0,
+          y: point.position.y - point.velocity.y * 10_000.0,
+        },
+        velocity: point.velocity.clone(),
+      })
+      .collect::<Vec<Point>>();
+
+    assert_eq!(Point::calculate_second_of_min_boundary(&points), 10_003);
+  }
+
+  #[test]
+  fn test_get_lit_positions_for_seconds() {
+    let points = get_example_points();
+    let lit = Point::get_lit_positions_for_seconds(&points, 3);
+
+    // the example message is "HI" in a smaller font
+    assert!(lit.contains(&(0, 0)));
+    assert!(lit.contains(&(4, 3)));
+    assert!(!lit.contains(&(1, 0)));
+    assert_eq!(Point::read_message_for_seconds(&points, 3), None);
+  }
 }
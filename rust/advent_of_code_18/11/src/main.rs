@@ -88,12 +88,15 @@ What is the X,Y,size identifier of the square with the largest total power?
 
 */
 
-use std::cmp::{max, min};
+mod summed_area_table;
+
+use summed_area_table::SummedAreaTable;
 
 type Unit = i64;
 type SerialNumber = Unit;
 
 const GRID_SERIAL_NUMBER: SerialNumber = 2568;
+const GRID_SIZE: usize = 300;
 
 #[derive(Debug, Clone, PartialEq)]
 struct Coord {
@@ -101,7 +104,7 @@ struct Coord {
   y: Unit,
 }
 
-fn calc_power_level_for_coord(coord: &mut Coord, serial_number: Unit) -> Unit {
+fn calc_power_level_for_coord(coord: &Coord, serial_number: Unit) -> Unit {
   let rack_id = coord.x + 10;
   let mut power_level = rack_id * coord.y;
 
@@ -117,103 +120,35 @@ fn calc_power_level_for_coord(coord: &mut Coord, serial_number: Unit) -> Unit {
   hundreds_digit - 5
 }
 
-// https://en.wikipedia.org/wiki/Summed-area_table
-fn get_summed_area_table(items: &mut Vec<Vec<Unit>>) -> Vec<Vec<Unit>> {
-  let mut summed_area_table: Vec<Vec<Unit>> = vec![];
-
-  for y in 0..(items.len()) {
-    let mut line = vec![];
-
-    for x in 0..items[y].len() {
-      let mut value = items[y][x];
-
-      if x > 0 {
-        value += line[x - 1];
-
-        if y > 0 {
-          value += summed_area_table[y - 1][x];
-          value -= summed_area_table[y - 1][x - 1];
-        }
-      } else if y > 0 {
-        value += summed_area_table[y - 1][x];
-      }
-
-      line.push(value);
-    }
+fn get_fuel_grid(serial_number: SerialNumber) -> SummedAreaTable {
+  SummedAreaTable::new(GRID_SIZE, GRID_SIZE, |x, y| {
+    let coord = Coord {
+      x: x as Unit,
+      y: y as Unit,
+    };
 
-    summed_area_table.push(line);
-  }
-
-  summed_area_table
+    calc_power_level_for_coord(&coord, serial_number)
+  })
 }
 
 fn calc_top_left_coord_of_max_power_level(
   serial_number: SerialNumber,
   square_size: Option<usize>,
 ) -> (Coord, usize) {
-  let mut power_levels: Vec<Vec<Unit>> = vec![];
-  let mut current_coord: Coord = Coord { x: 0, y: 0 };
-  let mut current_total_value = 0;
-  let mut current_size = 0;
-
-  for y in 0..300 {
-    let mut line = vec![];
-
-    for x in 0..300 {
-      let mut coord = Coord { x, y };
-      let value = calc_power_level_for_coord(&mut coord, serial_number);
-
-      line.push(value);
-    }
-
-    power_levels.push(line);
-  }
-
-  let summed_area_table = get_summed_area_table(&mut power_levels);
-
-  let (size_lower_bound, size_upper_bound) = match square_size {
-    Some(v) => (v, v + 1),
-    None => (1, 300),
-  };
-
-  for y in 0..300 {
-    for x in 0..300 {
-      let upper_bound = min(size_upper_bound, 300 - max(y, x) + 1);
-
-      if size_lower_bound >= upper_bound {
-        break;
-      }
-
-      for size in size_lower_bound..upper_bound {
-        let area_x = x + size - 1;
-        let area_y = y + size - 1;
-
-        let mut total_value = summed_area_table[area_y][area_x];
-
-        if x > 0 {
-          total_value -= summed_area_table[area_y][x - 1];
-
-          if y > 0 {
-            total_value -= summed_area_table[y - 1][area_x];
-            total_value += summed_area_table[y - 1][x - 1];
-          }
-        } else if y > 0 {
-          total_value -= summed_area_table[y - 1][area_x];
-        }
-
-        if current_total_value < total_value {
-          current_total_value = total_value;
-          current_size = size;
-          current_coord = Coord {
-            x: x as Unit,
-            y: y as Unit,
-          };
-        }
-      }
-    }
+  let fuel_grid = get_fuel_grid(serial_number);
+  let max_square = match square_size {
+    Some(size) => fuel_grid.get_max_square(size),
+    None => fuel_grid.get_max_square_any_size(),
   }
-
-  (current_coord, current_size)
+  .expect("The square doesn't fit in the grid");
+
+  (
+    Coord {
+      x: max_square.x as Unit,
+      y: max_square.y as Unit,
+    },
+    max_square.size,
+  )
 }
 
 fn main() {
@@ -255,8 +190,8 @@ mod tests {
   fn test_calc_power_level_for_coord() {
     let examples = get_examples_data_1();
 
-    for mut example in examples {
-      let value = calc_power_level_for_coord(&mut example.0, example.1);
+    for example in examples {
+      let value = calc_power_level_for_coord(&example.0, example.1);
 
       assert_eq!(value, example.2);
     }
@@ -284,29 +219,4 @@ mod tests {
       assert_eq!(size, example.2);
     }
   }
-
-  #[test]
-  fn test_get_summed_area_table() {
-    let mut initial_table = vec![
-      vec![31, 2, 4, 33, 5, 36],
-      vec![12, 26, 9, 10, 29, 25],
-      vec![13, 17, 21, 22, 20, 18],
-      vec![24, 23, 15, 16, 14, 19],
-      vec![30, 8, 28, 27, 11, 7],
-      vec![1, 35, 34, 3, 32, 6],
-    ];
-    let result = get_summed_area_table(&mut initial_table);
-
-    assert_eq!(
-      result,
-      vec![
-        vec![31, 33, 37, 70, 75, 111],
-        vec![43, 71, 84, 127, 161, 222],
-        vec![56, 101, 135, 200, 254, 333],
-        vec![80, 148, 197, 278, 346, 444],
-        vec![110, 186, 263, 371, 450, 555],
-        vec![111, 222, 333, 444, 555, 666],
-      ]
-    );
-  }
 }
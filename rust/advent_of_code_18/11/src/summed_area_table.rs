@@ -0,0 +1,156 @@
+// https://en.wikipedia.org/wiki/Summed-area_table
+
+pub type Value = i64;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Square {
+  pub x: usize,
+  pub y: usize,
+  pub size: usize,
+  pub sum: Value,
+}
+
+#[derive(Debug, Clone)]
+pub struct SummedAreaTable {
+  width: usize,
+  height: usize,
+  // it has an extra row and column of zeros at the top and left to avoid the edge cases
+  sums: Vec<Vec<Value>>,
+}
+
+impl SummedAreaTable {
+  pub fn new<F>(width: usize, height: usize, cell_value: F) -> SummedAreaTable
+  where
+    F: Fn(usize, usize) -> Value,
+  {
+    let mut sums: Vec<Vec<Value>> = vec![vec![0; width + 1]; height + 1];
+
+    for y in 0..height {
+      for x in 0..width {
+        sums[y + 1][x + 1] = cell_value(x, y) + sums[y][x + 1] + sums[y + 1][x] - sums[y][x];
+      }
+    }
+
+    SummedAreaTable {
+      width,
+      height,
+      sums,
+    }
+  }
+
+  pub fn get_rectangle_sum(&self, x: usize, y: usize, width: usize, height: usize) -> Value {
+    assert!(
+      x + width <= self.width && y + height <= self.height,
+      "The rectangle is outside of the table"
+    );
+
+    self.sums[y + height][x + width] - self.sums[y][x + width] - self.sums[y + height][x]
+      + self.sums[y][x]
+  }
+
+  // in case of ties the square with the lowest y, then x and then size is returned
+  pub fn get_max_square_with_sizes(&self, min_size: usize, max_size: usize) -> Option<Square> {
+    let mut max_square: Option<Square> = None;
+    let min_size = min_size.max(1);
+
+    for y in 0..self.height {
+      for x in 0..self.width {
+        let max_size_at_coord = max_size.min(self.width - x).min(self.height - y);
+
+        for size in min_size..=max_size_at_coord {
+          let sum = self.get_rectangle_sum(x, y, size, size);
+
+          if max_square.as_ref().is_none_or(|square| square.sum < sum) {
+            max_square = Some(Square { x, y, size, sum });
+          }
+        }
+      }
+    }
+
+    max_square
+  }
+
+  pub fn get_max_square(&self, size: usize) -> Option<Square> {
+    self.get_max_square_with_sizes(size, size)
+  }
+
+  pub fn get_max_square_any_size(&self) -> Option<Square> {
+    self.get_max_square_with_sizes(1, self.width.min(self.height))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn get_example_table() -> SummedAreaTable {
+    let rows = [
+      vec![31, 2, 4, 33, 5, 36],
+      vec![12, 26, 9, 10, 29, 25],
+      vec![13, 17, 21, 22, 20, 18],
+      vec![24, 23, 15, 16, 14, 19],
+      vec![30, 8, 28, 27, 11, 7],
+      vec![1, 35, 34, 3, 32, 6],
+    ];
+
+    SummedAreaTable::new(6, 6, |x, y| rows[y][x])
+  }
+
+  #[test]
+  fn test_get_prefix_sums() {
+    let table = get_example_table();
+    let expected = [
+      vec![31, 33, 37, 70, 75, 111],
+      vec![43, 71, 84, 127, 161, 222],
+      vec![56, 101, 135, 200, 254, 333],
+      vec![80, 148, 197, 278, 346, 444],
+      vec![110, 186, 263, 371, 450, 555],
+      vec![111, 222, 333, 444, 555, 666],
+    ];
+
+    for (y, row) in expected.iter().enumerate() {
+      for (x, sum) in row.iter().enumerate() {
+        assert_eq!(table.get_rectangle_sum(0, 0, x + 1, y + 1), *sum);
+      }
+    }
+  }
+
+  #[test]
+  fn test_get_rectangle_sum() {
+    let table = get_example_table();
+
+    assert_eq!(
+      table.get_rectangle_sum(2, 3, 3, 2),
+      15 + 16 + 14 + 28 + 27 + 11
+    );
+    assert_eq!(table.get_rectangle_sum(0, 0, 6, 6), 666);
+    assert_eq!(table.get_rectangle_sum(5, 5, 1, 1), 6);
+    assert_eq!(table.get_rectangle_sum(1, 1, 0, 3), 0);
+  }
+
+  #[test]
+  fn test_get_max_square() {
+    let table = SummedAreaTable::new(4, 3, |x, y| if x == 3 { -10 } else { (x * y) as Value });
+
+    assert_eq!(
+      table.get_max_square(2),
+      Some(Square {
+        x: 1,
+        y: 1,
+        size: 2,
+        sum: 1 + 2 + 2 + 4,
+      })
+    );
+    // the 3x3 square has the same sum as the 2x2 one, but it is found first
+    assert_eq!(
+      table.get_max_square_any_size(),
+      Some(Square {
+        x: 0,
+        y: 0,
+        size: 3,
+        sum: 9,
+      })
+    );
+    assert_eq!(table.get_max_square(4), None);
+  }
+}
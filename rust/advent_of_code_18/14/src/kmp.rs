@@ -0,0 +1,123 @@
+// https://en.wikipedia.org/wiki/Knuth%E2%80%93Morris%E2%80%93Pratt_algorithm
+
+#[derive(Debug, Clone)]
+pub struct PatternMatcher {
+  pattern: Vec<u8>,
+  // for each prefix of the pattern, the length of its longest proper prefix that is also a suffix
+  failure: Vec<usize>,
+  matched_len: usize,
+}
+
+impl PatternMatcher {
+  pub fn new(pattern: &[u8]) -> PatternMatcher {
+    assert!(!pattern.is_empty(), "The pattern can't be empty");
+
+    let mut failure: Vec<usize> = vec![0; pattern.len()];
+    let mut prefix_len = 0;
+
+    for idx in 1..pattern.len() {
+      while prefix_len > 0 && pattern[idx] != pattern[prefix_len] {
+        prefix_len = failure[prefix_len - 1];
+      }
+
+      if pattern[idx] == pattern[prefix_len] {
+        prefix_len += 1;
+      }
+
+      failure[idx] = prefix_len;
+    }
+
+    PatternMatcher {
+      pattern: pattern.to_vec(),
+      failure,
+      matched_len: 0,
+    }
+  }
+
+  // returns true when the value completes an occurrence of the pattern
+  pub fn push(&mut self, value: u8) -> bool {
+    if self.matched_len == self.pattern.len() {
+      self.matched_len = self.failure[self.matched_len - 1];
+    }
+
+    while self.matched_len > 0 && value != self.pattern[self.matched_len] {
+      self.matched_len = self.failure[self.matched_len - 1];
+    }
+
+    if value == self.pattern[self.matched_len] {
+      self.matched_len += 1;
+    }
+
+    self.matched_len == self.pattern.len()
+  }
+}
+
+// the index where the first occurrence of the pattern starts. It doesn't return if the iterator is
+// infinite and the pattern never appears
+pub fn find_first_occurrence<I>(values: I, pattern: &[u8]) -> Option<usize>
+where
+  I: IntoIterator<Item = u8>,
+{
+  let mut matcher = PatternMatcher::new(pattern);
+
+  for (idx, value) in values.into_iter().enumerate() {
+    if matcher.push(value) {
+      return Some(idx + 1 - pattern.len());
+    }
+  }
+
+  None
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn find_first_occurrence_naive(values: &[u8], pattern: &[u8]) -> Option<usize> {
+    values
+      .windows(pattern.len())
+      .position(|window| window == pattern)
+  }
+
+  #[test]
+  fn test_find_first_occurrence() {
+    assert_eq!(find_first_occurrence(vec![1, 1, 1, 2], &[1, 1, 2]), Some(1));
+    assert_eq!(
+      find_first_occurrence(vec![1, 2, 1, 2, 1, 3], &[1, 2, 1, 3]),
+      Some(2)
+    );
+    assert_eq!(find_first_occurrence(vec![1, 2, 1], &[2, 2]), None);
+    assert_eq!(find_first_occurrence((0..10).cycle(), &[9, 0, 1]), Some(9));
+  }
+
+  #[test]
+  fn test_matcher_overlapping_occurrences() {
+    let mut matcher = PatternMatcher::new(&[1, 1]);
+    let matches: Vec<bool> = [1, 1, 1, 0, 1].iter().map(|x| matcher.push(*x)).collect();
+
+    assert_eq!(matches, vec![false, true, true, false, false]);
+  }
+
+  // the sequences of 0 and 1 with `len` digits
+  fn get_binary_sequences(len: usize) -> Vec<Vec<u8>> {
+    (0..1 << len)
+      .map(|code: usize| (0..len).map(|idx| (code >> idx & 1) as u8).collect())
+      .collect()
+  }
+
+  // every pattern of up to 4 digits in every text of up to 8 digits
+  #[test]
+  fn test_find_first_occurrence_against_naive() {
+    for pattern in (1..=4).flat_map(get_binary_sequences) {
+      for values in (0..=8).flat_map(get_binary_sequences) {
+        assert_eq!(
+          find_first_occurrence(values.clone(), &pattern),
+          find_first_occurrence_naive(&values, &pattern),
+          "{:?} {:?}",
+          values,
+          pattern
+        );
+      }
+    }
+  }
+}
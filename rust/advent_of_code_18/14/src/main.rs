@@ -82,116 +82,126 @@ How many recipes appear on the scoreboard to the left of the score sequence in y
 
 */
 
+mod kmp;
+
+use kmp::find_first_occurrence;
+
 const INPUT_RECIPES_NUM: usize = 846_021;
 
 #[derive(Debug, Clone)]
-struct RecipesState {
-  recipes: Vec<usize>,
-  elf_1_pos: usize,
-  elf_2_pos: usize,
+struct Scoreboard {
+  recipes: Vec<u8>,
+  elves_positions: Vec<usize>,
 }
 
-fn get_initial_configuration() -> RecipesState {
-  RecipesState {
-    recipes: vec![3, 7],
-    elf_1_pos: 0,
-    elf_2_pos: 1,
-  }
+// yields the scores of all the recipes from the first one, creating new recipes when needed
+struct ScoreboardIter<'a> {
+  scoreboard: &'a mut Scoreboard,
+  idx: usize,
 }
 
-fn get_next_elf_idx(current_idx: usize, current_value: usize, recipes_len: usize) -> usize {
-  let moves = current_value + 1;
-  let mut new_idx = current_idx + moves;
+impl<'a> Iterator for ScoreboardIter<'a> {
+  type Item = u8;
 
-  while new_idx > recipes_len - 1 {
-    new_idx -= recipes_len;
-  }
+  fn next(&mut self) -> Option<u8> {
+    while self.idx >= self.scoreboard.recipes.len() {
+      self.scoreboard.create_new_recipes();
+    }
 
-  new_idx
-}
+    self.idx += 1;
 
-fn get_score_after_n_recipes(mut recipes_state: &mut RecipesState, n_steps: usize) -> String {
-  let mut recipes_len = 2;
+    Some(self.scoreboard.recipes[self.idx - 1])
+  }
+}
 
-  while recipes_len < n_steps + 10 {
-    recipes_len = update_recipes_state(&mut recipes_state, recipes_len);
+impl Scoreboard {
+  // each elf starts at the recipe with the same index
+  fn new(initial_recipes: &[u8], elves_num: usize) -> Scoreboard {
+    assert!(
+      elves_num > 0 && elves_num <= initial_recipes.len(),
+      "There must be at least one elf and one initial recipe per elf"
+    );
+
+    Scoreboard {
+      recipes: initial_recipes.to_vec(),
+      elves_positions: (0..elves_num).collect(),
+    }
   }
 
-  let slice = &recipes_state.recipes[n_steps..n_steps + 10];
-  let chars: Vec<String> = slice.iter().map(|x| x.to_string()).collect();
+  fn iter(&mut self) -> ScoreboardIter<'_> {
+    ScoreboardIter {
+      scoreboard: self,
+      idx: 0,
+    }
+  }
 
-  chars.join("")
-}
+  fn create_new_recipes(&mut self) {
+    let sum: usize = self
+      .elves_positions
+      .iter()
+      .map(|idx| self.recipes[*idx] as usize)
+      .sum();
+    let new_recipes_start = self.recipes.len();
+    let mut remaining = sum;
+
+    // the digits of the sum are pushed from the last one and then put in order
+    loop {
+      self.recipes.push((remaining % 10) as u8);
+      remaining /= 10;
+
+      if remaining == 0 {
+        break;
+      }
+    }
 
-fn update_recipes_state(recipes_state: &mut RecipesState, orig_recipes_len: usize) -> usize {
-  let mut recipes_len = orig_recipes_len;
-  let elf_1_value = recipes_state.recipes[recipes_state.elf_1_pos];
-  let elf_2_value = recipes_state.recipes[recipes_state.elf_2_pos];
-  let sum = elf_1_value + elf_2_value;
-
-  if sum > 9 {
-    let digit_2 = sum % 10;
-    let digit_1 = (sum - digit_2) / 10;
-
-    recipes_state.recipes.push(digit_1);
-    recipes_state.recipes.push(digit_2);
-    recipes_len += 2;
-  } else {
-    recipes_state.recipes.push(sum);
-    recipes_len += 1;
-  }
+    self.recipes[new_recipes_start..].reverse();
 
-  recipes_state.elf_1_pos = get_next_elf_idx(recipes_state.elf_1_pos, elf_1_value, recipes_len);
-  recipes_state.elf_2_pos = get_next_elf_idx(recipes_state.elf_2_pos, elf_2_value, recipes_len);
+    let recipes_len = self.recipes.len();
 
-  recipes_len
+    for idx in self.elves_positions.iter_mut() {
+      *idx = get_next_elf_idx(*idx, self.recipes[*idx], recipes_len);
+    }
+  }
 }
 
-fn get_n_recipes_when_score(mut recipes_state: &mut RecipesState, score: &str) -> usize {
-  let mut recipes_len = 2;
-  let mut last_tracked_idx = 0;
-  let digits: Vec<usize> = score
-    .chars()
-    .map(|x| x.to_string().parse::<usize>().unwrap())
-    .collect();
-  let digits_num = digits.clone().iter().count();
+fn get_next_elf_idx(current_idx: usize, current_value: u8, recipes_len: usize) -> usize {
+  let moves = current_value as usize + 1;
+  let mut new_idx = current_idx + moves;
 
-  loop {
-    recipes_len = update_recipes_state(&mut recipes_state, recipes_len);
-    let mut rel_idx = 0;
+  while new_idx > recipes_len - 1 {
+    new_idx -= recipes_len;
+  }
 
-    while last_tracked_idx + digits_num < recipes_len {
-      let idx = rel_idx + last_tracked_idx;
-      let value = recipes_state.recipes[idx];
+  new_idx
+}
 
-      if value != digits[rel_idx] {
-        last_tracked_idx += 1;
-        rel_idx = 0;
-      } else {
-        rel_idx += 1;
+fn get_initial_configuration() -> Scoreboard {
+  Scoreboard::new(&[3, 7], 2)
+}
 
-        if rel_idx == digits_num {
-          break;
-        }
-      }
-    }
+fn get_score_after_n_recipes(scoreboard: &mut Scoreboard, n_steps: usize) -> String {
+  scoreboard
+    .iter()
+    .skip(n_steps)
+    .take(10)
+    .map(|x| x.to_string())
+    .collect()
+}
 
-    if rel_idx == digits_num {
-      break;
-    }
-  }
+fn get_n_recipes_when_score(scoreboard: &mut Scoreboard, score: &str) -> usize {
+  let digits: Vec<u8> = score
+    .chars()
+    .map(|x| x.to_digit(10).expect("The score must only have digits") as u8)
+    .collect();
 
-  last_tracked_idx
+  find_first_occurrence(scoreboard.iter(), &digits).unwrap()
 }
 
 fn main() {
-  let initial_configuration = get_initial_configuration();
-  let first_exercise_result =
-    get_score_after_n_recipes(&mut initial_configuration.clone(), INPUT_RECIPES_NUM);
-  let second_exercise_result = get_n_recipes_when_score(
-    &mut initial_configuration.clone(),
-    &INPUT_RECIPES_NUM.to_string(),
-  );
+  let mut scoreboard = get_initial_configuration();
+  let first_exercise_result = get_score_after_n_recipes(&mut scoreboard, INPUT_RECIPES_NUM);
+  let second_exercise_result =
+    get_n_recipes_when_score(&mut scoreboard, &INPUT_RECIPES_NUM.to_string());
 
   println!("Results:");
   println!("- (1) score result: {}", first_exercise_result);
@@ -235,4 +245,31 @@ mod tests {
       assert_eq!(n_recipes, result.0);
     }
   }
+
+  #[test]
+  fn test_scoreboard_iter() {
+    let mut scoreboard = get_initial_configuration();
+    let scores: Vec<u8> = scoreboard.iter().take(20).collect();
+
+    assert_eq!(
+      scores,
+      vec![3, 7, 1, 0, 1, 0, 1, 2, 4, 5, 1, 5, 8, 9, 1, 6, 7, 7, 9, 2]
+    );
+    // a second iterator starts again from the first recipe
+    assert_eq!(scoreboard.iter().nth(19), Some(2));
+  }
+
+  #[test]
+  fn test_scoreboard_more_elves() {
+    let mut scoreboard = Scoreboard::new(&[3, 7, 1], 3);
+    let scores: Vec<u8> = scoreboard.iter().take(6).collect();
+
+    assert_eq!(scores, vec![3, 7, 1, 1, 1, 3]);
+
+    // twelve elves with a nine can create three recipes at once
+    let mut scoreboard = Scoreboard::new(&[9; 12], 12);
+    let scores: Vec<u8> = scoreboard.iter().skip(12).take(3).collect();
+
+    assert_eq!(scores, vec![1, 0, 8]);
+  }
 }
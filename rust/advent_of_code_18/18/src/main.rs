@@ -189,6 +189,7 @@ extern crate md5;
 
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::env;
 use std::fs::File;
 use std::io::prelude::*;
 
@@ -215,6 +216,71 @@ enum TerrainType {
 
 type MapTopology = HashMap<Coord, TerrainType>;
 
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+struct TerrainCounts {
+  ground: usize,
+  trees: usize,
+  lumberyards: usize,
+}
+
+impl TerrainCounts {
+  fn resource_value(&self) -> usize {
+    self.trees * self.lumberyards
+  }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+struct Cycle {
+  start: usize,
+  period: usize,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+struct History {
+  // the item at index N has the counts after N minutes
+  counts: Vec<TerrainCounts>,
+  cycle: Option<Cycle>,
+}
+
+impl History {
+  fn get_counts_at_minute(&self, minute: usize) -> Option<TerrainCounts> {
+    if minute < self.counts.len() {
+      return Some(self.counts[minute]);
+    }
+
+    let cycle = self.cycle?;
+    let index = cycle.start + (minute - cycle.start) % cycle.period;
+
+    Some(self.counts[index])
+  }
+
+  fn to_csv(&self) -> String {
+    let mut csv =
+      "minute,ground,trees,lumberyards,resource_value,cycle_start,cycle_period\n".to_string();
+
+    for (minute, counts) in self.counts.iter().enumerate() {
+      let cycle_cols = match self.cycle {
+        Some(cycle) if minute >= cycle.start + cycle.period => {
+          format!("{},{}", cycle.start, cycle.period)
+        }
+        _ => ",".to_string(),
+      };
+
+      csv.push_str(&format!(
+        "{},{},{},{},{},{}\n",
+        minute,
+        counts.ground,
+        counts.trees,
+        counts.lumberyards,
+        counts.resource_value(),
+        cycle_cols
+      ));
+    }
+
+    csv
+  }
+}
+
 #[derive(Debug, Clone, Eq, PartialEq)]
 struct Map {
   topology: MapTopology,
@@ -339,7 +405,7 @@ impl Map {
             }
           }
           TerrainType::Lumberyard => {
-            let mut found_trees = 0;;
+            let mut found_trees = 0;
             let mut found_lumberyards = 0;
 
             for other_coord in Map::get_surrounding_coords(&coord, &self.boundary).iter() {
@@ -387,19 +453,26 @@ impl Map {
     md5::compute(full_str)
   }
 
-  fn evolve_n_minutes_with_history(&mut self, minutes: usize) {
+  fn evolve_n_minutes_with_history(&mut self, minutes: usize) -> History {
     let mut history: HashMap<md5::Digest, usize> = HashMap::new();
+    let mut counts: Vec<TerrainCounts> = vec![];
+    let mut cycle: Option<Cycle> = None;
 
     for minute in 0..minutes {
       let serialized = self.serialize();
 
-      if history.get(&serialized).is_some() {
-        let num = history[&serialized];
+      counts.push(self.count_terrains());
 
+      if let Some(num) = history.get(&serialized) {
         let interval = minute - num;
         let remaining_total = minutes - minute;
         let remainder = remaining_total % interval;
 
+        cycle = Some(Cycle {
+          start: *num,
+          period: interval,
+        });
+
         for _ in 0..remainder {
           self.evolve_one_minute();
         }
@@ -411,29 +484,85 @@ impl Map {
 
       self.evolve_one_minute();
     }
+
+    if cycle.is_none() {
+      counts.push(self.count_terrains());
+    }
+
+    History { counts, cycle }
   }
 
-  fn count_resource_value(&self) -> usize {
-    let mut lumberyards = 0;
-    let mut trees = 0;
+  fn count_terrains(&self) -> TerrainCounts {
+    let mut counts = TerrainCounts {
+      ground: 0,
+      trees: 0,
+      lumberyards: 0,
+    };
 
     for y in 0..=self.boundary.max_y {
       for x in 0..=self.boundary.max_x {
         let coord = Coord { x, y };
 
         match self.topology[&coord] {
+          TerrainType::Ground => {
+            counts.ground += 1;
+          }
           TerrainType::Lumberyard => {
-            lumberyards += 1;
+            counts.lumberyards += 1;
           }
           TerrainType::Trees => {
-            trees += 1;
+            counts.trees += 1;
           }
-          _ => {}
         }
       }
     }
 
-    lumberyards * trees
+    counts
+  }
+
+  fn count_resource_value(&self) -> usize {
+    self.count_terrains().resource_value()
+  }
+
+  fn to_ppm(&self, scale: usize) -> Vec<u8> {
+    let width = (self.boundary.max_x + 1) * scale;
+    let height = (self.boundary.max_y + 1) * scale;
+    let mut bytes: Vec<u8> = format!("P6\n{} {}\n255\n", width, height).into_bytes();
+
+    for y in 0..height {
+      for x in 0..width {
+        let coord = Coord {
+          x: x / scale,
+          y: y / scale,
+        };
+        let color: [u8; 3] = match self.topology[&coord] {
+          TerrainType::Ground => [194, 178, 128],
+          TerrainType::Trees => [34, 139, 34],
+          TerrainType::Lumberyard => [139, 69, 19],
+        };
+
+        bytes.extend_from_slice(&color);
+      }
+    }
+
+    bytes
+  }
+
+  fn write_frames(&self, dir: &str, minutes: usize, scale: usize) {
+    let mut map = self.clone();
+
+    std::fs::create_dir_all(dir).expect("Unable to create the frames directory");
+
+    for minute in 0..=minutes {
+      let path = format!("{}/minute_{:05}.ppm", dir, minute);
+      let mut file = File::create(&path).expect("Unable to create the frame file");
+
+      file
+        .write_all(&map.to_ppm(scale))
+        .expect("Unable to write the frame file");
+
+      map.evolve_one_minute();
+    }
   }
 }
 
@@ -473,11 +602,31 @@ fn main() {
   let mut map_2 = get_input_map();
 
   map.evolve_n_minutes_with_history(10);
-  map_2.evolve_n_minutes_with_history(1_000_000_000);
+  let history = map_2.evolve_n_minutes_with_history(1_000_000_000);
+  let args: Vec<String> = env::args().skip(1).collect();
 
   println!("Results");
   println!("- (1) resource value: {}", map.count_resource_value());
-  println!("- (2) resource value: {}", map_2.count_resource_value());
+  println!(
+    "- (2) resource value: {}",
+    history
+      .get_counts_at_minute(1_000_000_000)
+      .unwrap()
+      .resource_value()
+  );
+
+  // `cargo run -- export`
+  if args.first().map(|x| x.as_ref()) == Some("export") {
+    let mut stats_file = File::create("stats.csv").expect("Unable to create the stats file");
+    stats_file
+      .write_all(history.to_csv().as_bytes())
+      .expect("Unable to write the stats file");
+
+    let frames_num = history.counts.len() - 1;
+    get_input_map().write_frames("frames", frames_num, 2);
+
+    println!("- wrote stats.csv and {} frames in frames/", frames_num + 1);
+  }
 }
 
 #[cfg(test)]
@@ -545,4 +694,80 @@ mod tests {
 
     assert_eq!(result.count_resource_value(), 1147);
   }
+
+  #[test]
+  fn test_history_counts() {
+    let text = get_example_data();
+    let mut result = Map::from_string(&text);
+
+    let history = result.evolve_n_minutes_with_history(10);
+
+    assert_eq!(history.counts.len(), 11);
+    assert_eq!(
+      history.counts[0],
+      TerrainCounts {
+        ground: 56,
+        trees: 27,
+        lumberyards: 17,
+      }
+    );
+    assert_eq!(history.counts[10].resource_value(), 1147);
+    assert_eq!(
+      history.get_counts_at_minute(10),
+      Some(result.count_terrains())
+    );
+  }
+
+  #[test]
+  fn test_history_cycle() {
+    let text = get_example_data();
+    let mut result = Map::from_string(&text);
+    let mut result_2 = Map::from_string(&text);
+
+    let history = result.evolve_n_minutes_with_history(1_000);
+    let cycle = history.cycle.unwrap();
+
+    result_2.evolve_n_minutes_with_history(cycle.start + cycle.period * 3 + 1);
+
+    assert_eq!(
+      history.get_counts_at_minute(cycle.start + cycle.period * 3 + 1),
+      Some(result_2.count_terrains())
+    );
+    assert_eq!(
+      history.get_counts_at_minute(1_000),
+      Some(result.count_terrains())
+    );
+  }
+
+  #[test]
+  fn test_history_to_csv() {
+    let text = get_example_data();
+    let mut result = Map::from_string(&text);
+
+    let csv = result.evolve_n_minutes_with_history(10).to_csv();
+    let lines: Vec<&str> = csv.lines().collect();
+
+    assert_eq!(
+      lines[0],
+      "minute,ground,trees,lumberyards,resource_value,cycle_start,cycle_period"
+    );
+    assert_eq!(lines[11], "10,32,37,31,1147,,");
+  }
+
+  #[test]
+  fn test_map_to_ppm() {
+    let text = get_example_data();
+    let result = Map::from_string(&text);
+
+    let ppm = result.to_ppm(2);
+    let header = "P6\n20 20\n255\n";
+
+    assert_eq!(&ppm[..header.len()], header.as_bytes());
+    assert_eq!(ppm.len(), header.len() + 20 * 20 * 3);
+    // the coord (1, 0) is a lumberyard
+    assert_eq!(
+      &ppm[header.len() + 2 * 3..header.len() + 3 * 3],
+      &[139, 69, 19]
+    );
+  }
 }
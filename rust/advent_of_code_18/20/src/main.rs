@@ -190,13 +190,20 @@ doors?
 
 extern crate pathfinding;
 
+mod route_regex;
+
+use std::collections::hash_map::Entry;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::collections::VecDeque;
 use std::fs::File;
 use std::io::prelude::*;
 use std::iter::FromIterator;
 
-use pathfinding::directed::dijkstra::dijkstra_all;
+use pathfinding::directed::bfs::bfs;
+
+use route_regex::{format_route_regex, parse_route_regex, Direction, ParseError, RouteExpr};
 
 type LengthUnit = i32;
 
@@ -222,6 +229,7 @@ enum TerrainType {
 }
 
 type MapTopology = HashMap<Coord, TerrainType>;
+type DoorsToRoom = HashMap<Coord, usize>;
 
 #[derive(Debug)]
 struct Map {
@@ -231,52 +239,28 @@ struct Map {
 }
 
 impl Map {
-  fn add_topology_terrain(&mut self, current_coord: Coord, ch: char) -> Coord {
-    let coords = match ch {
-      'E' => Some((
-        Coord {
-          x: current_coord.x + 1,
-          y: current_coord.y,
-        },
-        Coord {
-          x: current_coord.x + 2,
-          y: current_coord.y,
-        },
-      )),
-      'W' => Some((
-        Coord {
-          x: current_coord.x - 1,
-          y: current_coord.y,
-        },
-        Coord {
-          x: current_coord.x - 2,
-          y: current_coord.y,
-        },
-      )),
-      'N' => Some((
-        Coord {
-          x: current_coord.x,
-          y: current_coord.y - 1,
-        },
-        Coord {
-          x: current_coord.x,
-          y: current_coord.y - 2,
-        },
-      )),
-      'S' => Some((
-        Coord {
-          x: current_coord.x,
-          y: current_coord.y + 1,
-        },
-        Coord {
-          x: current_coord.x,
-          y: current_coord.y + 2,
-        },
-      )),
-      _ => None,
+  fn get_coords_in_direction(coord: Coord, direction: Direction) -> (Coord, Coord) {
+    let (dx, dy) = match direction {
+      Direction::East => (1, 0),
+      Direction::West => (-1, 0),
+      Direction::North => (0, -1),
+      Direction::South => (0, 1),
     };
 
-    let (door_coord, next_coord) = coords.unwrap();
+    let door_coord = Coord {
+      x: coord.x + dx,
+      y: coord.y + dy,
+    };
+    let next_coord = Coord {
+      x: coord.x + dx * 2,
+      y: coord.y + dy * 2,
+    };
+
+    (door_coord, next_coord)
+  }
+
+  fn add_topology_terrain(&mut self, current_coord: Coord, direction: Direction) -> Coord {
+    let (door_coord, next_coord) = Map::get_coords_in_direction(current_coord, direction);
 
     self.topology.insert(next_coord, TerrainType::Room);
     self.topology.insert(door_coord, TerrainType::Door);
@@ -284,11 +268,37 @@ impl Map {
     next_coord
   }
 
-  fn new_from_directions_str(directions: &str) -> Self {
+  fn apply_route_expr(&mut self, expr: &RouteExpr, starts: HashSet<Coord>) -> HashSet<Coord> {
+    match expr {
+      RouteExpr::Move(direction) => starts
+        .iter()
+        .map(|coord| self.add_topology_terrain(*coord, *direction))
+        .collect(),
+      RouteExpr::Sequence(items) => {
+        let mut current_coords = starts;
+
+        for item in items {
+          current_coords = self.apply_route_expr(item, current_coords);
+        }
+
+        current_coords
+      }
+      RouteExpr::Alternation(options) => {
+        let mut end_coords: HashSet<Coord> = HashSet::new();
+
+        for option in options {
+          end_coords.extend(self.apply_route_expr(option, starts.clone()));
+        }
+
+        end_coords
+      }
+      RouteExpr::Empty => starts,
+    }
+  }
+
+  fn new_from_route_expr(expr: &RouteExpr) -> Self {
     let mut topology: MapTopology = HashMap::new();
-    let chs: Vec<char> = directions.chars().collect();
-    let mut current_coord = Coord { x: 0, y: 0 };
-    let mut paths: Vec<Coord> = vec![];
+    let starting_coord = Coord { x: 0, y: 0 };
     let boundary = Boundary {
       max_x: 0,
       max_y: 0,
@@ -296,35 +306,132 @@ impl Map {
       min_y: 0,
     };
 
-    topology.insert(current_coord, TerrainType::Room);
+    topology.insert(starting_coord, TerrainType::Room);
 
     let mut map = Map {
       topology,
       boundary,
-      directions: directions.to_string(),
+      directions: format_route_regex(expr),
     };
 
-    for ch in chs {
-      match ch {
-        'N' | 'S' | 'E' | 'W' => {
-          current_coord = map.add_topology_terrain(current_coord, ch);
-        }
-        '(' => {
-          paths.push(current_coord);
+    map.apply_route_expr(expr, HashSet::from_iter(vec![starting_coord]));
+    map.apply_walls();
+
+    map
+  }
+
+  fn new_from_directions_str(directions: &str) -> Result<Self, ParseError> {
+    let expr = parse_route_regex(directions)?;
+
+    Ok(Map::new_from_route_expr(&expr))
+  }
+
+  fn get_door_directions(&self, coord: Coord) -> Vec<Direction> {
+    Direction::all()
+      .iter()
+      .filter(|direction| {
+        let (door_coord, _) = Map::get_coords_in_direction(coord, **direction);
+
+        self.topology.get(&door_coord) == Some(&TerrainType::Door)
+      })
+      .cloned()
+      .collect()
+  }
+
+  // walks every door exactly once, so the number of directions in the result is the number of
+  // doors in the map, which is the least needed to describe it
+  fn get_route_expr(&self) -> RouteExpr {
+    let mut visited_rooms: HashSet<Coord> = HashSet::new();
+    let mut visited_doors: HashSet<Coord> = HashSet::new();
+
+    self.get_route_expr_from(Coord { x: 0, y: 0 }, &mut visited_rooms, &mut visited_doors)
+  }
+
+  fn get_route_expr_from(
+    &self,
+    start: Coord,
+    visited_rooms: &mut HashSet<Coord>,
+    visited_doors: &mut HashSet<Coord>,
+  ) -> RouteExpr {
+    fn get_items(expr: RouteExpr) -> Vec<RouteExpr> {
+      match expr {
+        RouteExpr::Sequence(items) => items,
+        RouteExpr::Empty => vec![],
+        other => vec![other],
+      }
+    }
+
+    let mut items: Vec<RouteExpr> = vec![];
+    let mut current_coord = start;
+
+    visited_rooms.insert(start);
+
+    loop {
+      let candidates: Vec<Direction> = self
+        .get_door_directions(current_coord)
+        .into_iter()
+        .filter(|direction| {
+          let (door_coord, _) = Map::get_coords_in_direction(current_coord, *direction);
+
+          !visited_doors.contains(&door_coord)
+        })
+        .collect();
+
+      // corridors are walked iteratively so only the branches add recursion
+      if candidates.len() == 1 {
+        let (door_coord, next_coord) = Map::get_coords_in_direction(current_coord, candidates[0]);
+
+        visited_doors.insert(door_coord);
+        items.push(RouteExpr::Move(candidates[0]));
+
+        if visited_rooms.contains(&next_coord) {
+          break;
         }
-        ')' => {
-          current_coord = paths.pop().unwrap();
+
+        visited_rooms.insert(next_coord);
+        current_coord = next_coord;
+
+        continue;
+      }
+
+      let mut branches: Vec<RouteExpr> = vec![];
+
+      for direction in candidates {
+        let (door_coord, next_coord) = Map::get_coords_in_direction(current_coord, direction);
+
+        if visited_doors.contains(&door_coord) {
+          continue;
         }
-        '|' => {
-          current_coord = *paths.last().unwrap();
+
+        visited_doors.insert(door_coord);
+
+        let mut branch_items = vec![RouteExpr::Move(direction)];
+
+        if !visited_rooms.contains(&next_coord) {
+          branch_items.extend(get_items(self.get_route_expr_from(
+            next_coord,
+            visited_rooms,
+            visited_doors,
+          )));
         }
-        _ => {}
+
+        branches.push(RouteExpr::Sequence(branch_items));
       }
+
+      if branches.len() == 1 {
+        items.extend(get_items(branches.pop().unwrap()));
+      } else if !branches.is_empty() {
+        items.push(RouteExpr::Alternation(branches));
+      }
+
+      break;
     }
 
-    map.apply_walls();
+    if items.is_empty() {
+      return RouteExpr::Empty;
+    }
 
-    map
+    RouteExpr::Sequence(items)
   }
 
   fn get_topology_boundary(&self) -> Boundary {
@@ -366,9 +473,7 @@ impl Map {
     for y in min_y..=max_y {
       for x in min_x..=max_x {
         let coord = Coord { x, y };
-        if self.topology.get(&coord).is_none() {
-          self.topology.insert(coord, TerrainType::Wall);
-        }
+        self.topology.entry(coord).or_insert(TerrainType::Wall);
       }
     }
 
@@ -394,39 +499,34 @@ impl Map {
     '|'
   }
 
-  fn get_part_1_and_2(&self) -> (usize, usize) {
-    let min_doors_to_count = 1000;
-    let starting_coord = Coord { x: 0, y: 0 };
+  // doors are the edges of the graph, so a breadth-first search gives the least doors to each room
+  fn get_doors_from_room(&self, start: Coord) -> DoorsToRoom {
+    let mut doors_to_room: DoorsToRoom = HashMap::new();
+    let mut queue: VecDeque<Coord> = VecDeque::new();
 
-    fn get_successors(map: &Map, coord: Coord) -> Vec<(Coord, usize)> {
-      let next_rooms = map.get_rooms_next_to_coord(coord);
-      let mut successors: Vec<(Coord, usize)> = vec![];
+    doors_to_room.insert(start, 0);
+    queue.push_back(start);
 
-      for next_room in next_rooms {
-        successors.push((next_room, 1))
-      }
+    while let Some(coord) = queue.pop_front() {
+      let doors_num = doors_to_room[&coord];
 
-      successors
-    }
-
-    let all_reachable_rooms = dijkstra_all(&starting_coord, |x| get_successors(&self, *x));
-
-    let mut part_1 = 0;
-    let mut part_2 = 0;
-
-    for coord in all_reachable_rooms.clone().keys() {
-      let doors_num = all_reachable_rooms[coord].1;
-
-      if part_1 < doors_num {
-        part_1 = doors_num;
-      }
-
-      if doors_num >= min_doors_to_count {
-        part_2 += 1;
+      for next_room in self.get_rooms_next_to_coord(coord) {
+        if let Entry::Vacant(entry) = doors_to_room.entry(next_room) {
+          entry.insert(doors_num + 1);
+          queue.push_back(next_room);
+        }
       }
     }
 
-    (part_1, part_2)
+    doors_to_room
+  }
+
+  fn get_shortest_path(&self, from: Coord, to: Coord) -> Option<Vec<Coord>> {
+    bfs(
+      &from,
+      |coord| self.get_rooms_next_to_coord(*coord),
+      |coord| *coord == to,
+    )
   }
 
   fn get_rooms_next_to_coord(&self, coord: Coord) -> HashSet<Coord> {
@@ -478,21 +578,36 @@ impl Map {
   }
 
   fn get_representation(&self) -> String {
+    self.get_path_representation(&[])
+  }
+
+  // the rooms and doors of the path are drawn with '*'
+  fn get_path_representation(&self, path: &[Coord]) -> String {
+    let mut path_coords: HashSet<Coord> = HashSet::from_iter(path.iter().cloned());
     let mut lines: Vec<String> = vec![];
 
+    for rooms in path.windows(2) {
+      path_coords.insert(Coord {
+        x: (rooms[0].x + rooms[1].x) / 2,
+        y: (rooms[0].y + rooms[1].y) / 2,
+      });
+    }
+
     for y in self.boundary.min_y..=self.boundary.max_y {
       let mut line: Vec<char> = vec![];
 
       for x in self.boundary.min_x..=self.boundary.max_x {
         let coord = Coord { x, y };
-        let ch = if coord.x != 0 || coord.y != 0 {
+        let ch = if coord.x == 0 && coord.y == 0 {
+          'X'
+        } else if path_coords.contains(&coord) {
+          '*'
+        } else {
           match self.topology[&coord] {
             TerrainType::Wall => '#',
             TerrainType::Door => self.get_door_char(coord),
             TerrainType::Room => '.',
           }
-        } else {
-          'X'
         };
 
         line.push(ch);
@@ -517,6 +632,44 @@ impl std::fmt::Display for Map {
   }
 }
 
+fn get_farthest_rooms(doors_to_room: &DoorsToRoom) -> (usize, Vec<Coord>) {
+  let max_doors = doors_to_room.values().cloned().max().unwrap_or(0);
+
+  (max_doors, get_rooms_at_doors(doors_to_room, max_doors))
+}
+
+fn get_rooms_at_doors(doors_to_room: &DoorsToRoom, doors_num: usize) -> Vec<Coord> {
+  let mut rooms: Vec<Coord> = doors_to_room
+    .iter()
+    .filter(|(_, doors)| **doors == doors_num)
+    .map(|(coord, _)| *coord)
+    .collect();
+
+  rooms.sort_by_key(|coord| (coord.y, coord.x));
+
+  rooms
+}
+
+fn get_doors_histogram(doors_to_room: &DoorsToRoom) -> BTreeMap<usize, usize> {
+  let mut histogram: BTreeMap<usize, usize> = BTreeMap::new();
+
+  for doors_num in doors_to_room.values() {
+    *histogram.entry(*doors_num).or_insert(0) += 1;
+  }
+
+  histogram
+}
+
+fn get_part_1_and_2(doors_to_room: &DoorsToRoom, min_doors_to_count: usize) -> (usize, usize) {
+  let (part_1, _) = get_farthest_rooms(doors_to_room);
+  let part_2 = get_doors_histogram(doors_to_room)
+    .range(min_doors_to_count..)
+    .map(|(_, rooms_num)| rooms_num)
+    .sum();
+
+  (part_1, part_2)
+}
+
 fn get_input_map() -> Map {
   let mut file = File::open("src/input.txt").expect("Unable to open the file");
   let mut contents = String::new();
@@ -524,16 +677,43 @@ fn get_input_map() -> Map {
     .read_to_string(&mut contents)
     .expect("Unable to read the file");
 
-  Map::new_from_directions_str(&contents)
+  match Map::new_from_directions_str(&contents) {
+    Ok(map) => map,
+    Err(err) => panic!("Invalid directions: {}", err),
+  }
 }
 
 fn main() {
   let map = get_input_map();
-  let (min_doors, rooms_num) = map.get_part_1_and_2();
+  let starting_coord = Coord { x: 0, y: 0 };
+  let doors_to_room = map.get_doors_from_room(starting_coord);
+  let (min_doors, rooms_num) = get_part_1_and_2(&doors_to_room, 1000);
+  let (_, farthest_rooms) = get_farthest_rooms(&doors_to_room);
+  let path = map
+    .get_shortest_path(starting_coord, farthest_rooms[0])
+    .unwrap();
+  let histogram = get_doors_histogram(&doors_to_room);
+  let most_common_doors = histogram.iter().max_by_key(|(_, rooms)| **rooms).unwrap();
 
   println!("Results:");
   println!("- (1) min doors for longest path: {}", min_doors);
   println!("- (2) rooms number: {}", rooms_num);
+  println!(
+    "- farthest room: ({}, {}), path through {} rooms",
+    farthest_rooms[0].x,
+    farthest_rooms[0].y,
+    path.len()
+  );
+  println!(
+    "- most common distance: {} doors ({} rooms)",
+    most_common_doors.0, most_common_doors.1
+  );
+  // `directions` is the input regex formatted after parsing, not the raw file contents
+  println!(
+    "- parsed input regex length: {}, regenerated regex length: {}",
+    map.directions.len(),
+    format_route_regex(&map.get_route_expr()).len()
+  );
 }
 
 #[cfg(test)]
@@ -541,11 +721,11 @@ mod tests {
   use super::*;
 
   fn get_example_data_1() -> Map {
-    Map::new_from_directions_str("^WNE$")
+    Map::new_from_directions_str("^WNE$").unwrap()
   }
 
   fn get_example_data_2() -> Map {
-    Map::new_from_directions_str("^ENWWW(NEEE|SSE(EE|N))$")
+    Map::new_from_directions_str("^ENWWW(NEEE|SSE(EE|N))$").unwrap()
   }
 
   #[test]
@@ -576,21 +756,138 @@ mod tests {
     );
   }
 
+  fn get_map_part_1_and_2(map: &Map, min_doors_to_count: usize) -> (usize, usize) {
+    get_part_1_and_2(
+      &map.get_doors_from_room(Coord { x: 0, y: 0 }),
+      min_doors_to_count,
+    )
+  }
+
   #[test]
   fn test_get_part_1_and_2() {
-    assert_eq!(get_example_data_1().get_part_1_and_2().0, 3);
-    assert_eq!(get_example_data_2().get_part_1_and_2().0, 10);
+    assert_eq!(get_map_part_1_and_2(&get_example_data_1(), 1000).0, 3);
+    assert_eq!(get_map_part_1_and_2(&get_example_data_2(), 1000).0, 10);
     assert_eq!(
-      Map::new_from_directions_str("^ENNWSWW(NEWS|)SSSEEN(WNSE|)EE(SWEN|)NNN$")
-        .get_part_1_and_2()
-        .0,
+      get_map_part_1_and_2(
+        &Map::new_from_directions_str("^ENNWSWW(NEWS|)SSSEEN(WNSE|)EE(SWEN|)NNN$").unwrap(),
+        1000
+      )
+      .0,
       18
     );
     assert_eq!(
-      Map::new_from_directions_str("^ESSWWN(E|NNENN(EESS(WNSE|)SSS|WWWSSSSE(SW|NNNE)))$")
-        .get_part_1_and_2()
-        .0,
+      get_map_part_1_and_2(
+        &Map::new_from_directions_str("^ESSWWN(E|NNENN(EESS(WNSE|)SSS|WWWSSSSE(SW|NNNE)))$")
+          .unwrap(),
+        1000
+      )
+      .0,
       23
     );
   }
+
+  #[test]
+  fn test_new_from_directions_str_error() {
+    let err = Map::new_from_directions_str("^EN(W|S$").unwrap_err();
+
+    assert_eq!(err.position, 3);
+  }
+
+  #[test]
+  fn test_get_route_expr() {
+    assert_eq!(
+      format_route_regex(&get_example_data_1().get_route_expr()),
+      "^WNE$"
+    );
+    assert_eq!(
+      format_route_regex(&get_example_data_2().get_route_expr()),
+      "^ENWWW(NEEE|SSE(N|EE))$"
+    );
+
+    for text in &[
+      "^ENNWSWW(NEWS|)SSSEEN(WNSE|)EE(SWEN|)NNN$",
+      "^ESSWWN(E|NNENN(EESS(WNSE|)SSS|WWWSSSSE(SW|NNNE)))$",
+      "^WSSEESWWWNW(S|NENNEEEENN(ESSSSW(NWSW|SSEN)|WSWWN(E|WWS(E|SS))))$",
+      "^N(E|W)N(E|W)SSS$",
+    ] {
+      let map = Map::new_from_directions_str(text).unwrap();
+      let regenerated = format_route_regex(&map.get_route_expr());
+      let regenerated_map = Map::new_from_directions_str(&regenerated).unwrap();
+
+      assert_eq!(
+        regenerated_map.get_representation(),
+        map.get_representation()
+      );
+      assert_eq!(
+        regenerated.chars().filter(|ch| ch.is_alphabetic()).count(),
+        map
+          .topology
+          .values()
+          .filter(|terrain| **terrain == TerrainType::Door)
+          .count()
+      );
+    }
+  }
+
+  #[test]
+  fn test_get_part_1_and_2_threshold() {
+    let doors_to_room = get_example_data_2().get_doors_from_room(Coord { x: 0, y: 0 });
+
+    assert_eq!(get_part_1_and_2(&doors_to_room, 0).1, 16);
+    assert_eq!(get_part_1_and_2(&doors_to_room, 10).1, 1);
+    assert_eq!(get_part_1_and_2(&doors_to_room, 11).1, 0);
+  }
+
+  #[test]
+  fn test_get_doors_from_room() {
+    let map = get_example_data_1();
+    let doors_to_room = map.get_doors_from_room(Coord { x: -2, y: -2 });
+
+    assert_eq!(doors_to_room[&Coord { x: -2, y: -2 }], 0);
+    assert_eq!(doors_to_room[&Coord { x: 0, y: 0 }], 2);
+    assert_eq!(doors_to_room[&Coord { x: 0, y: -2 }], 1);
+  }
+
+  #[test]
+  fn test_get_farthest_rooms_and_histogram() {
+    let doors_to_room = get_example_data_2().get_doors_from_room(Coord { x: 0, y: 0 });
+
+    assert_eq!(
+      get_farthest_rooms(&doors_to_room),
+      (10, vec![Coord { x: 2, y: 2 }])
+    );
+    assert_eq!(
+      get_rooms_at_doors(&doors_to_room, 8),
+      vec![Coord { x: 0, y: -4 }, Coord { x: -2, y: 2 }]
+    );
+
+    let histogram = get_doors_histogram(&doors_to_room);
+
+    assert_eq!(histogram.values().sum::<usize>(), 16);
+    assert_eq!(histogram[&0], 1);
+    assert_eq!(histogram[&9], 3);
+    assert_eq!(histogram[&10], 1);
+  }
+
+  #[test]
+  fn test_get_path_representation() {
+    let map = get_example_data_2();
+    let path = map
+      .get_shortest_path(Coord { x: 0, y: 0 }, Coord { x: 2, y: 2 })
+      .unwrap();
+
+    assert_eq!(path.len(), 11);
+    assert_eq!(
+      map.get_path_representation(&path),
+      "#########
+#.|.|.|.#
+#-#######
+#*******#
+#*#####*#
+#*#.#X**#
+#*#-#####
+#*******#
+#########"
+    );
+  }
 }
@@ -0,0 +1,293 @@
+use std::fmt;
+
+#[derive(Debug, Hash, Eq, PartialEq, Clone, Copy, PartialOrd, Ord)]
+pub enum Direction {
+  North,
+  East,
+  South,
+  West,
+}
+
+impl Direction {
+  pub fn all() -> [Direction; 4] {
+    [
+      Direction::North,
+      Direction::East,
+      Direction::South,
+      Direction::West,
+    ]
+  }
+
+  pub fn from_char(ch: char) -> Option<Direction> {
+    match ch {
+      'N' => Some(Direction::North),
+      'E' => Some(Direction::East),
+      'S' => Some(Direction::South),
+      'W' => Some(Direction::West),
+      _ => None,
+    }
+  }
+
+  pub fn to_char(self) -> char {
+    match self {
+      Direction::North => 'N',
+      Direction::East => 'E',
+      Direction::South => 'S',
+      Direction::West => 'W',
+    }
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum RouteExpr {
+  Move(Direction),
+  Sequence(Vec<RouteExpr>),
+  Alternation(Vec<RouteExpr>),
+  Empty,
+}
+
+impl fmt::Display for RouteExpr {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      RouteExpr::Move(direction) => write!(f, "{}", direction.to_char()),
+      RouteExpr::Sequence(items) => {
+        for item in items {
+          write!(f, "{}", item)?;
+        }
+
+        Ok(())
+      }
+      RouteExpr::Alternation(options) => {
+        write!(f, "(")?;
+
+        for (idx, option) in options.iter().enumerate() {
+          if idx != 0 {
+            write!(f, "|")?;
+          }
+
+          write!(f, "{}", option)?;
+        }
+
+        write!(f, ")")
+      }
+      RouteExpr::Empty => Ok(()),
+    }
+  }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum ParseErrorKind {
+  MissingStart,
+  MissingEnd,
+  UnexpectedChar(char),
+  UnclosedBranch,
+  UnmatchedClose,
+  TrailingInput,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub struct ParseError {
+  pub position: usize,
+  pub kind: ParseErrorKind,
+}
+
+impl fmt::Display for ParseError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let reason = match self.kind {
+      ParseErrorKind::MissingStart => "expected '^' at the start".to_string(),
+      ParseErrorKind::MissingEnd => "expected '$' at the end".to_string(),
+      ParseErrorKind::UnexpectedChar(ch) => format!("unexpected character '{}'", ch),
+      ParseErrorKind::UnclosedBranch => "branch opened here is never closed".to_string(),
+      ParseErrorKind::UnmatchedClose => "')' without a matching '('".to_string(),
+      ParseErrorKind::TrailingInput => "unexpected input after '$'".to_string(),
+    };
+
+    write!(f, "{} (position {})", reason, self.position)
+  }
+}
+
+struct Parser {
+  chars: Vec<char>,
+  position: usize,
+}
+
+impl Parser {
+  fn peek(&self) -> Option<char> {
+    self.chars.get(self.position).cloned()
+  }
+
+  fn error(&self, kind: ParseErrorKind) -> ParseError {
+    ParseError {
+      position: self.position,
+      kind,
+    }
+  }
+
+  // consumes directions and branches until it finds one of '|', ')' or '$' (or the end)
+  fn parse_sequence(&mut self) -> Result<RouteExpr, ParseError> {
+    let mut items: Vec<RouteExpr> = vec![];
+
+    while let Some(ch) = self.peek() {
+      match ch {
+        '(' => {
+          items.push(self.parse_alternation()?);
+        }
+        '|' | ')' | '$' => break,
+        _ => match Direction::from_char(ch) {
+          Some(direction) => {
+            items.push(RouteExpr::Move(direction));
+            self.position += 1;
+          }
+          None => return Err(self.error(ParseErrorKind::UnexpectedChar(ch))),
+        },
+      }
+    }
+
+    if items.is_empty() {
+      return Ok(RouteExpr::Empty);
+    }
+
+    Ok(RouteExpr::Sequence(items))
+  }
+
+  fn parse_alternation(&mut self) -> Result<RouteExpr, ParseError> {
+    let open_position = self.position;
+    let mut options: Vec<RouteExpr> = vec![];
+
+    self.position += 1;
+
+    loop {
+      options.push(self.parse_sequence()?);
+
+      match self.peek() {
+        Some('|') => {
+          self.position += 1;
+        }
+        Some(')') => {
+          self.position += 1;
+
+          return Ok(RouteExpr::Alternation(options));
+        }
+        _ => {
+          return Err(ParseError {
+            position: open_position,
+            kind: ParseErrorKind::UnclosedBranch,
+          });
+        }
+      }
+    }
+  }
+
+  fn parse_regex(&mut self) -> Result<RouteExpr, ParseError> {
+    if self.peek() != Some('^') {
+      return Err(self.error(ParseErrorKind::MissingStart));
+    }
+
+    self.position += 1;
+
+    let expr = self.parse_sequence()?;
+
+    match self.peek() {
+      Some('$') => {
+        self.position += 1;
+      }
+      Some('|') => return Err(self.error(ParseErrorKind::UnexpectedChar('|'))),
+      Some(')') => return Err(self.error(ParseErrorKind::UnmatchedClose)),
+      _ => return Err(self.error(ParseErrorKind::MissingEnd)),
+    }
+
+    while let Some(ch) = self.peek() {
+      if !ch.is_whitespace() {
+        return Err(self.error(ParseErrorKind::TrailingInput));
+      }
+
+      self.position += 1;
+    }
+
+    Ok(expr)
+  }
+}
+
+pub fn parse_route_regex(text: &str) -> Result<RouteExpr, ParseError> {
+  let mut parser = Parser {
+    chars: text.chars().collect(),
+    position: 0,
+  };
+
+  parser.parse_regex()
+}
+
+pub fn format_route_regex(expr: &RouteExpr) -> String {
+  format!("^{}$", expr)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn parse_and_format(text: &str) -> String {
+    format_route_regex(&parse_route_regex(text).unwrap())
+  }
+
+  #[test]
+  fn test_parse_route_regex_ast() {
+    assert_eq!(
+      parse_route_regex("^N(E|)$"),
+      Ok(RouteExpr::Sequence(vec![
+        RouteExpr::Move(Direction::North),
+        RouteExpr::Alternation(vec![
+          RouteExpr::Sequence(vec![RouteExpr::Move(Direction::East)]),
+          RouteExpr::Empty,
+        ]),
+      ]))
+    );
+    assert_eq!(parse_route_regex("^$"), Ok(RouteExpr::Empty));
+  }
+
+  #[test]
+  fn test_parse_route_regex_round_trip() {
+    for text in &[
+      "^WNE$",
+      "^ENWWW(NEEE|SSE(EE|N))$",
+      "^ENNWSWW(NEWS|)SSSEEN(WNSE|)EE(SWEN|)NNN$",
+      "^ESSWWN(E|NNENN(EESS(WNSE|)SSS|WWWSSSSE(SW|NNNE)))$",
+    ] {
+      assert_eq!(parse_and_format(text), *text);
+    }
+
+    assert_eq!(parse_and_format("^WNE$\n"), "^WNE$");
+  }
+
+  #[test]
+  fn test_parse_route_regex_errors() {
+    let cases = vec![
+      ("WNE$", 0, ParseErrorKind::MissingStart),
+      ("^WNE", 4, ParseErrorKind::MissingEnd),
+      ("^WXE$", 2, ParseErrorKind::UnexpectedChar('X')),
+      ("^W(N|E$", 2, ParseErrorKind::UnclosedBranch),
+      ("^W(N(S|E$", 4, ParseErrorKind::UnclosedBranch),
+      ("^WN)E$", 3, ParseErrorKind::UnmatchedClose),
+      ("^W|E$", 2, ParseErrorKind::UnexpectedChar('|')),
+      ("^WE$N", 4, ParseErrorKind::TrailingInput),
+    ];
+
+    for (text, position, kind) in cases {
+      assert_eq!(
+        parse_route_regex(text),
+        Err(ParseError { position, kind }),
+        "{}",
+        text
+      );
+    }
+  }
+
+  #[test]
+  fn test_parse_error_display() {
+    let err = parse_route_regex("^W(N|E$").unwrap_err();
+
+    assert_eq!(
+      err.to_string(),
+      "branch opened here is never closed (position 2)"
+    );
+  }
+}
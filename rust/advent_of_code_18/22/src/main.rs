@@ -406,9 +406,12 @@ What is the fewest number of minutes you can take to reach the target?
 extern crate pathfinding;
 
 use std::collections::HashMap;
+use std::env;
+use std::fs::File;
+use std::io::prelude::*;
 use std::iter::FromIterator;
 
-use pathfinding::directed::dijkstra::dijkstra_all;
+use pathfinding::directed::astar::astar;
 
 type LengthUnit = usize;
 
@@ -424,7 +427,7 @@ struct Boundary {
   max_y: LengthUnit,
 }
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 enum TerrainType {
   Rocky,
   Wet,
@@ -434,7 +437,6 @@ enum TerrainType {
 #[derive(Debug, Copy, Clone)]
 struct Terrain {
   terrain_type: TerrainType,
-  geological_index: usize,
   erosion_level: usize,
 }
 
@@ -447,6 +449,98 @@ enum EquipmentTool {
   Torch,
 }
 
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+struct FindingState {
+  coord: Coord,
+  equipment: EquipmentTool,
+}
+
+#[derive(Debug, Clone)]
+struct RescueRules {
+  allowed_tools: HashMap<TerrainType, Vec<EquipmentTool>>,
+  move_cost: usize,
+  switch_cost: usize,
+  start_tool: EquipmentTool,
+  target_tool: EquipmentTool,
+}
+
+impl Default for RescueRules {
+  fn default() -> Self {
+    let mut allowed_tools: HashMap<TerrainType, Vec<EquipmentTool>> = HashMap::new();
+
+    allowed_tools.insert(
+      TerrainType::Rocky,
+      vec![EquipmentTool::ClimbingGear, EquipmentTool::Torch],
+    );
+    allowed_tools.insert(
+      TerrainType::Wet,
+      vec![EquipmentTool::ClimbingGear, EquipmentTool::None],
+    );
+    allowed_tools.insert(
+      TerrainType::Narrow,
+      vec![EquipmentTool::Torch, EquipmentTool::None],
+    );
+
+    RescueRules {
+      allowed_tools,
+      move_cost: 1,
+      switch_cost: 7,
+      start_tool: EquipmentTool::Torch,
+      target_tool: EquipmentTool::Torch,
+    }
+  }
+}
+
+impl RescueRules {
+  fn get_allowed_tools(&self, terrain_type: TerrainType) -> &[EquipmentTool] {
+    match self.allowed_tools.get(&terrain_type) {
+      Some(tools) => tools,
+      None => &[],
+    }
+  }
+
+  fn is_tool_allowed(&self, terrain_type: TerrainType, equipment: EquipmentTool) -> bool {
+    self.get_allowed_tools(terrain_type).contains(&equipment)
+  }
+}
+
+fn manhattan_distance(coord_a: &Coord, coord_b: &Coord) -> usize {
+  coord_a.x.abs_diff(coord_b.x) + coord_a.y.abs_diff(coord_b.y)
+}
+
+fn get_route_description(route: &[FindingState], rules: &RescueRules) -> String {
+  let mut lines: Vec<String> = vec![];
+  let mut minutes = 0;
+
+  for (idx, finding_state) in route.iter().enumerate() {
+    if idx == 0 {
+      lines.push(format!(
+        "{:>5}: start at {},{} with {:?}",
+        minutes, finding_state.coord.x, finding_state.coord.y, finding_state.equipment
+      ));
+      continue;
+    }
+
+    let prev_finding_state = &route[idx - 1];
+
+    if prev_finding_state.equipment != finding_state.equipment {
+      minutes += rules.switch_cost;
+      lines.push(format!(
+        "{:>5}: switch to {:?} at {},{}",
+        minutes, finding_state.equipment, finding_state.coord.x, finding_state.coord.y
+      ));
+    } else {
+      minutes += rules.move_cost;
+      lines.push(format!(
+        "{:>5}: move to {},{}",
+        minutes, finding_state.coord.x, finding_state.coord.y
+      ));
+    }
+  }
+
+  lines.join("\n")
+}
+
 struct Map {
   topology: Topology,
   target: Coord,
@@ -456,14 +550,10 @@ struct Map {
 impl Map {
   fn new(depth: usize, target: &Coord, opt_boundary: Option<Boundary>) -> Self {
     let mut topology: Topology = HashMap::new();
-    let boundary = if opt_boundary.is_some() {
-      opt_boundary.unwrap()
-    } else {
-      Boundary {
-        max_x: target.x,
-        max_y: target.y,
-      }
-    };
+    let boundary = opt_boundary.unwrap_or(Boundary {
+      max_x: target.x,
+      max_y: target.y,
+    });
 
     for y in 0..=boundary.max_y {
       for x in 0..=boundary.max_x {
@@ -487,7 +577,6 @@ impl Map {
             _ => TerrainType::Rocky,
           },
           erosion_level: erosion_level as usize,
-          geological_index,
         };
         topology.insert(coord, terrain);
       }
@@ -519,8 +608,29 @@ impl Map {
   }
 
   fn get_print_text(&self) -> String {
+    self.get_route_print_text(&[])
+  }
+
+  // the regions of the route show the tool used in them: 't' (torch), 'c' (climbing gear) or
+  // 'n' (neither), and in uppercase where the tool was switched
+  fn get_route_print_text(&self, route: &[FindingState]) -> String {
+    let mut route_chars: HashMap<Coord, char> = HashMap::new();
     let mut lines: Vec<String> = vec![];
 
+    for (idx, finding_state) in route.iter().enumerate() {
+      let mut ch = match finding_state.equipment {
+        EquipmentTool::Torch => 't',
+        EquipmentTool::ClimbingGear => 'c',
+        EquipmentTool::None => 'n',
+      };
+
+      if idx > 0 && route[idx - 1].equipment != finding_state.equipment {
+        ch = ch.to_ascii_uppercase();
+      }
+
+      route_chars.insert(finding_state.coord, ch);
+    }
+
     for y in 0..=self.boundary.max_y {
       let mut line: Vec<char> = vec![];
 
@@ -537,6 +647,8 @@ impl Map {
           ch = 'M';
         } else if coord == self.target {
           ch = 'T';
+        } else if let Some(route_ch) = route_chars.get(&coord) {
+          ch = *route_ch;
         }
 
         line.push(ch);
@@ -556,179 +668,363 @@ impl Map {
       println!("{}", line);
     }
   }
+}
 
-  fn calc_least_minutes_to_target(&self) -> usize {
-    #[derive(Debug, Clone, Eq, PartialEq, Hash)]
-    struct FindingState {
-      coord: Coord,
-      equipment: EquipmentTool,
-    };
+// unlike `Map`, which precomputes every region inside a boundary, the cave only computes (and
+// keeps) the erosion levels of the regions that are requested, plus the ones they depend on
+struct Cave {
+  depth: usize,
+  target: Coord,
+  erosion_levels: HashMap<Coord, usize>,
+}
 
-    let init_finding_state = FindingState {
-      coord: Coord { x: 0, y: 0 },
-      equipment: EquipmentTool::Torch,
-    };
+impl Cave {
+  fn new(depth: usize, target: &Coord) -> Self {
+    Cave {
+      depth,
+      target: *target,
+      erosion_levels: HashMap::new(),
+    }
+  }
 
-    fn get_contiguous_coords(map: &Map, coord: &Coord) -> Vec<Coord> {
-      let mut coords: Vec<Coord> = vec![];
+  fn get_erosion_level(&mut self, coord: &Coord) -> usize {
+    if let Some(erosion_level) = self.erosion_levels.get(coord) {
+      return *erosion_level;
+    }
 
-      if coord.x > 0 {
-        coords.push(Coord {
-          x: coord.x - 1,
-          y: coord.y,
-        });
-      }
-      if coord.y > 0 {
-        coords.push(Coord {
-          x: coord.x,
-          y: coord.y - 1,
-        });
-      }
-      if coord.x < map.boundary.max_x {
-        coords.push(Coord {
-          x: coord.x + 1,
-          y: coord.y,
-        });
+    // iterative instead of recursive, as the dependency chain can be as long as `x + y`
+    let mut pending: Vec<Coord> = vec![*coord];
+
+    while let Some(current) = pending.last().cloned() {
+      if self.erosion_levels.contains_key(&current) {
+        pending.pop();
+        continue;
       }
-      if coord.y < map.boundary.max_y {
-        coords.push(Coord {
-          x: coord.x,
-          y: coord.y + 1,
-        });
+
+      let geological_index = if current == (Coord { x: 0, y: 0 }) || current == self.target {
+        Some(0)
+      } else if current.y == 0 {
+        Some(current.x * 16807)
+      } else if current.x == 0 {
+        Some(current.y * 48271)
+      } else {
+        let coord_left = Coord {
+          x: current.x - 1,
+          y: current.y,
+        };
+        let coord_above = Coord {
+          x: current.x,
+          y: current.y - 1,
+        };
+
+        match (
+          self.erosion_levels.get(&coord_left),
+          self.erosion_levels.get(&coord_above),
+        ) {
+          (Some(left), Some(above)) => Some(left * above),
+          (left, above) => {
+            if left.is_none() {
+              pending.push(coord_left);
+            }
+            if above.is_none() {
+              pending.push(coord_above);
+            }
+
+            None
+          }
+        }
+      };
+
+      if let Some(geological_index) = geological_index {
+        self
+          .erosion_levels
+          .insert(current, (self.depth + geological_index) % 20183);
+        pending.pop();
       }
+    }
 
-      coords
+    self.erosion_levels[coord]
+  }
+
+  fn get_terrain_type(&mut self, coord: &Coord) -> TerrainType {
+    match self.get_erosion_level(coord) % 3 {
+      0 => TerrainType::Rocky,
+      1 => TerrainType::Wet,
+      _ => TerrainType::Narrow,
     }
+  }
+
+  fn get_cached_regions_num(&self) -> usize {
+    self.erosion_levels.len()
+  }
+
+  fn get_cache_bytes(&self) -> usize {
+    self.erosion_levels.capacity() * std::mem::size_of::<(Coord, usize)>()
+  }
+
+  fn get_contiguous_coords(coord: &Coord) -> Vec<Coord> {
+    let mut coords: Vec<Coord> = vec![];
 
-    fn get_terrain_of_coord(map: &Map, coord: &Coord) -> TerrainType {
-      map.topology[coord].terrain_type
+    if coord.x > 0 {
+      coords.push(Coord {
+        x: coord.x - 1,
+        y: coord.y,
+      });
+    }
+    if coord.y > 0 {
+      coords.push(Coord {
+        x: coord.x,
+        y: coord.y - 1,
+      });
     }
+    // there is no right or bottom boundary: the regions are computed when reached
+    coords.push(Coord {
+      x: coord.x + 1,
+      y: coord.y,
+    });
+    coords.push(Coord {
+      x: coord.x,
+      y: coord.y + 1,
+    });
+
+    coords
+  }
 
-    fn get_successors(map: &Map, finding_state: &FindingState) -> Vec<(FindingState, usize)> {
-      let contiguous_coords = get_contiguous_coords(&map, &finding_state.coord);
-      let current_terrain_type = get_terrain_of_coord(&map, &finding_state.coord);
-      let mut options: Vec<(FindingState, usize)> = vec![];
-
-      for contiguous_coord in contiguous_coords {
-        let terrain_type = get_terrain_of_coord(&map, &contiguous_coord);
-
-        if terrain_type != current_terrain_type {
-          if terrain_type == TerrainType::Rocky && finding_state.equipment == EquipmentTool::None {
-            options.push((
-              FindingState {
-                coord: contiguous_coord,
-                equipment: if current_terrain_type == TerrainType::Narrow {
-                  EquipmentTool::Torch
-                } else {
-                  EquipmentTool::ClimbingGear
-                },
-              },
-              8,
-            ));
-            continue;
-          }
-          if terrain_type == TerrainType::Wet && finding_state.equipment == EquipmentTool::Torch {
-            options.push((
-              FindingState {
-                coord: contiguous_coord,
-                equipment: if current_terrain_type == TerrainType::Narrow {
-                  EquipmentTool::None
-                } else {
-                  EquipmentTool::ClimbingGear
-                },
-              },
-              8,
-            ));
-            continue;
-          }
-          if terrain_type == TerrainType::Narrow
-            && finding_state.equipment == EquipmentTool::ClimbingGear
-          {
-            options.push((
-              FindingState {
-                coord: contiguous_coord,
-                equipment: if current_terrain_type == TerrainType::Wet {
-                  EquipmentTool::None
-                } else {
-                  EquipmentTool::Torch
-                },
-              },
-              8,
-            ));
-            continue;
-          }
-        }
+  fn get_successors(
+    &mut self,
+    rules: &RescueRules,
+    finding_state: &FindingState,
+    limit: &Boundary,
+    reaches_limit: &mut bool,
+  ) -> Vec<(FindingState, usize)> {
+    let current_terrain_type = self.get_terrain_type(&finding_state.coord);
+    let mut options: Vec<(FindingState, usize)> = vec![];
+
+    for equipment in rules.get_allowed_tools(current_terrain_type) {
+      if *equipment != finding_state.equipment {
+        options.push((
+          FindingState {
+            coord: finding_state.coord,
+            equipment: *equipment,
+          },
+          rules.switch_cost,
+        ));
+      }
+    }
+
+    for contiguous_coord in Cave::get_contiguous_coords(&finding_state.coord) {
+      let terrain_type = self.get_terrain_type(&contiguous_coord);
+
+      if !rules.is_tool_allowed(terrain_type, finding_state.equipment) {
+        continue;
+      }
 
+      if contiguous_coord.x > limit.max_x || contiguous_coord.y > limit.max_y {
+        *reaches_limit = true;
+      } else {
         options.push((
           FindingState {
             coord: contiguous_coord,
             equipment: finding_state.equipment,
           },
-          1,
+          rules.move_cost,
         ));
       }
+    }
 
-      options
+    options
+  }
+
+  // The cave has no boundary, so the search would never end when the target can't be reached. It
+  // looks for a route inside a box around the target, doubling it while the search is cut by its
+  // edges, so it only ends without a route when the reachable regions are all inside the box. The
+  // minutes of a route bound how far the fastest one can go: every region of it is at least its
+  // distance to the mouth in moves, which needs moves with a cost
+  fn find_fastest_route(
+    &mut self,
+    rules: &RescueRules,
+  ) -> Result<Option<(Vec<FindingState>, usize)>, String> {
+    if rules.move_cost == 0 {
+      return Err("The moves need a cost to bound the route search".to_string());
     }
 
-    let dummy_finding = (
-      FindingState {
-        coord: Coord { x: 0, y: 0 },
-        equipment: EquipmentTool::None,
-      },
-      100_000_000,
+    let mut box_size = std::cmp::max(self.target.x + self.target.y, 1);
+
+    loop {
+      let box_limit = Boundary {
+        max_x: box_size,
+        max_y: box_size,
+      };
+
+      match self.find_fastest_route_within(rules, &box_limit) {
+        (Some((route, minutes)), _) => {
+          let max_distance = minutes / rules.move_cost;
+
+          if max_distance <= box_size {
+            return Ok(Some((route, minutes)));
+          }
+
+          let bound_limit = Boundary {
+            max_x: max_distance,
+            max_y: max_distance,
+          };
+
+          return Ok(self.find_fastest_route_within(rules, &bound_limit).0);
+        }
+        (None, false) => return Ok(None),
+        (None, true) => box_size *= 2,
+      }
+    }
+  }
+
+  // the fastest route inside the limit, and if the limit cut the search
+  fn find_fastest_route_within(
+    &mut self,
+    rules: &RescueRules,
+    limit: &Boundary,
+  ) -> (Option<(Vec<FindingState>, usize)>, bool) {
+    let init_finding_state = FindingState {
+      coord: Coord { x: 0, y: 0 },
+      equipment: rules.start_tool,
+    };
+    let target_finding_state = FindingState {
+      coord: self.target,
+      equipment: rules.target_tool,
+    };
+    let target = self.target;
+
+    if !rules.is_tool_allowed(self.get_terrain_type(&target), rules.target_tool)
+      || !rules.is_tool_allowed(
+        self.get_terrain_type(&init_finding_state.coord),
+        rules.start_tool,
+      )
+    {
+      return (None, false);
+    }
+
+    // it never overestimates: every step costs at least `move_cost` and a different tool
+    // requires at least one switch
+    let get_heuristic = |finding_state: &FindingState| {
+      let distance = manhattan_distance(&finding_state.coord, &target_finding_state.coord);
+      let switch = if finding_state.equipment == target_finding_state.equipment {
+        0
+      } else {
+        rules.switch_cost
+      };
+
+      distance * rules.move_cost + switch
+    };
+
+    let mut reaches_limit = false;
+    let route = astar(
+      &init_finding_state,
+      |x| self.get_successors(rules, x, limit, &mut reaches_limit),
+      get_heuristic,
+      |x| *x == target_finding_state,
     );
 
-    let all_results = dijkstra_all(&init_finding_state, |x| get_successors(&self, &x));
-    let result_with_torch = all_results
-      .get(&FindingState {
-        coord: self.target,
-        equipment: EquipmentTool::Torch,
-      })
-      .unwrap_or(&dummy_finding)
-      .1;
-    let result_with_none = all_results
-      .get(&FindingState {
-        coord: self.target,
-        equipment: EquipmentTool::None,
-      })
-      .unwrap_or(&dummy_finding)
-      .1
-      + 7;
-    let result_with_climbing = all_results
-      .get(&FindingState {
-        coord: self.target,
-        equipment: EquipmentTool::ClimbingGear,
-      })
-      .unwrap_or(&dummy_finding)
-      .1
-      + 7;
-
-    std::cmp::min(
-      result_with_torch,
-      std::cmp::min(result_with_climbing, result_with_none),
-    )
+    (route, reaches_limit)
+  }
+
+  fn calc_least_minutes_to_target(&mut self) -> usize {
+    let (_, least_minutes) = self
+      .find_fastest_route(&RescueRules::default())
+      .unwrap()
+      .unwrap();
+
+    least_minutes
   }
 }
 
-const INPUT_DEPTH: usize = 6969;
-const INPUT_TARGET: Coord = Coord { x: 9, y: 796 };
+// compares the regions (and approximate bytes) stored by the eager grid, using a boundary that
+// gives room to the path, with the ones stored by the lazy cave after finding the path
+fn print_memory_benchmark(depth: usize, target: &Coord, boundary: Boundary) {
+  use std::time::Instant;
+
+  let start = Instant::now();
+  let map = Map::new(depth, target, Some(boundary));
+  let map_duration = start.elapsed();
+  let map_bytes = map.topology.capacity() * std::mem::size_of::<(Coord, Terrain)>();
+
+  let start = Instant::now();
+  let mut cave = Cave::new(depth, target);
+  cave.calc_least_minutes_to_target();
+  let cave_duration = start.elapsed();
+
+  println!("Memory benchmark:");
+  println!(
+    "- eager grid: {} regions, ~{} KB, built in {:?}",
+    map.topology.len(),
+    map_bytes / 1024,
+    map_duration
+  );
+  println!(
+    "- lazy cave: {} regions, ~{} KB, built while searching in {:?}",
+    cave.get_cached_regions_num(),
+    cave.get_cache_bytes() / 1024,
+    cave_duration
+  );
+}
 
-fn main() {
-  let map = Map::new(
-    INPUT_DEPTH,
-    &INPUT_TARGET,
+// the description of the route and its map, which is as big as needed for it
+fn write_route_file(depth: usize, target: &Coord, route: &[FindingState], rules: &RescueRules) {
+  let route_map = Map::new(
+    depth,
+    target,
     Some(Boundary {
-      max_x: INPUT_TARGET.x + 100,
-      max_y: INPUT_TARGET.y + 100,
+      max_x: route.iter().map(|x| x.coord.x).max().unwrap(),
+      max_y: route.iter().map(|x| x.coord.y).max().unwrap(),
     }),
   );
+  let mut file = File::create("route.txt").expect("Unable to create the route file");
+
+  file
+    .write_all(
+      format!(
+        "{}\n\n{}\n",
+        get_route_description(route, rules),
+        route_map.get_route_print_text(route)
+      )
+      .as_bytes(),
+    )
+    .expect("Unable to write the route file");
+}
+
+const INPUT_DEPTH: usize = 6969;
+const INPUT_TARGET: Coord = Coord { x: 9, y: 796 };
+
+fn main() {
+  let args: Vec<String> = env::args().skip(1).collect();
+
+  // `cargo run --release -- benchmark`
+  if args.first().map(|x| x.as_ref()) == Some("benchmark") {
+    print_memory_benchmark(
+      INPUT_DEPTH,
+      &INPUT_TARGET,
+      Boundary {
+        max_x: INPUT_TARGET.x + 100,
+        max_y: INPUT_TARGET.y + 100,
+      },
+    );
+
+    return;
+  }
+
+  let map = Map::new(INPUT_DEPTH, &INPUT_TARGET, None);
+  let mut cave = Cave::new(INPUT_DEPTH, &INPUT_TARGET);
   let risk_level = map.calc_risk_level();
-  let least_minutes = map.calc_least_minutes_to_target();
+  let rules = RescueRules::default();
+  let (route, least_minutes) = cave.find_fastest_route(&rules).unwrap().unwrap();
 
   println!("Results:");
   println!("- (1) rist level: {}", risk_level);
   println!("- (2) least minutes: {}", least_minutes);
+
+  // `cargo run --release -- route`
+  if args.first().map(|x| x.as_ref()) == Some("route") {
+    write_route_file(INPUT_DEPTH, &INPUT_TARGET, &route, &rules);
+    println!("- wrote the route in route.txt");
+  }
 }
 
 #[cfg(test)]
@@ -779,9 +1075,201 @@ mod tests {
   }
 
   #[test]
-  fn test_calc_least_minutes_to_target() {
+  fn test_cave_matches_map() {
     let map = get_example_map();
-    let least_minutes = map.calc_least_minutes_to_target();
+    let mut cave = Cave::new(510, &Coord { x: 10, y: 10 });
+
+    for y in 0..=15 {
+      for x in 0..=15 {
+        let coord = Coord { x, y };
+
+        assert_eq!(
+          cave.get_erosion_level(&coord),
+          map.topology[&coord].erosion_level
+        );
+        assert_eq!(
+          cave.get_terrain_type(&coord),
+          map.topology[&coord].terrain_type
+        );
+      }
+    }
+  }
+
+  #[test]
+  fn test_cave_lazy_erosion_level() {
+    let mut cave = Cave::new(510, &Coord { x: 10, y: 10 });
+
+    assert_eq!(cave.get_erosion_level(&Coord { x: 1, y: 1 }), 1805);
+    assert_eq!(cave.get_cached_regions_num(), 3);
+    assert_eq!(cave.get_erosion_level(&Coord { x: 0, y: 1 }), 8415);
+    assert_eq!(cave.get_cached_regions_num(), 3);
+  }
+
+  #[test]
+  fn test_rescue_rules() {
+    let rules = RescueRules::default();
+
+    assert!(rules.is_tool_allowed(TerrainType::Rocky, EquipmentTool::Torch));
+    assert!(!rules.is_tool_allowed(TerrainType::Wet, EquipmentTool::Torch));
+    assert!(!rules.is_tool_allowed(TerrainType::Narrow, EquipmentTool::ClimbingGear));
+  }
+
+  #[test]
+  fn test_find_fastest_route() {
+    let rules = RescueRules::default();
+    let mut cave = Cave::new(510, &Coord { x: 10, y: 10 });
+    let (route, least_minutes) = cave.find_fastest_route(&rules).unwrap().unwrap();
+    let switches_num = route
+      .windows(2)
+      .filter(|x| x[0].equipment != x[1].equipment)
+      .count();
+
+    assert_eq!(least_minutes, 45);
+    assert_eq!(
+      least_minutes,
+      switches_num * 7 + (route.len() - 1 - switches_num)
+    );
+    assert_eq!(route[0].coord, Coord { x: 0, y: 0 });
+    assert_eq!(route[route.len() - 1].coord, Coord { x: 10, y: 10 });
+    assert_eq!(route[route.len() - 1].equipment, EquipmentTool::Torch);
+
+    for finding_state in route.iter() {
+      assert!(rules.is_tool_allowed(
+        cave.get_terrain_type(&finding_state.coord),
+        finding_state.equipment
+      ));
+    }
+
+    let description = get_route_description(&route, &rules);
+
+    assert_eq!(description.lines().count(), route.len());
+    assert!(description.lines().last().unwrap().starts_with("   45: "));
+
+    let map = get_example_map();
+    let text = map.get_route_print_text(&route);
+    let lines: Vec<Vec<char>> = text.lines().map(|line| line.chars().collect()).collect();
+    let route_coords: std::collections::HashSet<Coord> = route.iter().map(|x| x.coord).collect();
+
+    assert_eq!(lines[0][0], 'M');
+    assert_eq!(lines[10][10], 'T');
+
+    for (y, line) in lines.iter().enumerate() {
+      for (x, ch) in line.iter().enumerate() {
+        let coord = Coord { x, y };
+
+        if coord != (Coord { x: 0, y: 0 }) && coord != map.target {
+          assert_eq!("tcnTCN".contains(*ch), route_coords.contains(&coord));
+        }
+      }
+    }
+  }
+
+  #[test]
+  fn test_find_fastest_route_with_custom_rules() {
+    let mut rules = RescueRules::default();
+    let mut cave = Cave::new(510, &Coord { x: 10, y: 10 });
+
+    rules.switch_cost = 1;
+    let (_, least_minutes) = cave.find_fastest_route(&rules).unwrap().unwrap();
+
+    assert!(least_minutes < 45);
+    assert!(least_minutes >= 20);
+
+    // the target is rocky, where the torch is needed
+    rules
+      .allowed_tools
+      .insert(TerrainType::Rocky, vec![EquipmentTool::ClimbingGear]);
+
+    assert_eq!(cave.find_fastest_route(&rules), Ok(None));
+  }
+
+  // the rocky regions around the mouth and the target are not connected, and the other ones don't
+  // allow any tool
+  #[test]
+  fn test_find_fastest_route_unreachable() {
+    let mut rules = RescueRules::default();
+    let mut cave = Cave::new(510, &Coord { x: 10, y: 10 });
+
+    rules
+      .allowed_tools
+      .insert(TerrainType::Rocky, vec![EquipmentTool::Torch]);
+    rules.allowed_tools.insert(TerrainType::Wet, vec![]);
+    rules.allowed_tools.remove(&TerrainType::Narrow);
+
+    assert_eq!(cave.find_fastest_route(&rules), Ok(None));
+  }
+
+  // with slow switches the fastest route can go out of the first box, but not out of the bound
+  #[test]
+  fn test_find_fastest_route_bound() {
+    let mut rules = RescueRules::default();
+    let mut cave = Cave::new(510, &Coord { x: 10, y: 10 });
+
+    rules.switch_cost = 100;
+
+    let (_, least_minutes) = cave.find_fastest_route(&rules).unwrap().unwrap();
+    let (_, least_minutes_in_big_box) = cave
+      .find_fastest_route_within(
+        &rules,
+        &Boundary {
+          max_x: 200,
+          max_y: 200,
+        },
+      )
+      .0
+      .unwrap();
+
+    assert_eq!(least_minutes, least_minutes_in_big_box);
+  }
+
+  // the wet regions don't allow any tool, so the route to the target has to go around them out of
+  // the first box
+  #[test]
+  fn test_find_fastest_route_out_of_first_box() {
+    let mut rules = RescueRules::default();
+    let target = Coord { x: 3, y: 1 };
+    let mut cave = Cave::new(38, &target);
+
+    rules
+      .allowed_tools
+      .insert(TerrainType::Rocky, vec![EquipmentTool::Torch]);
+    rules.allowed_tools.insert(TerrainType::Wet, vec![]);
+    rules
+      .allowed_tools
+      .insert(TerrainType::Narrow, vec![EquipmentTool::Torch]);
+
+    let first_box = Boundary {
+      max_x: target.x + target.y,
+      max_y: target.x + target.y,
+    };
+
+    assert_eq!(
+      cave.find_fastest_route_within(&rules, &first_box),
+      (None, true)
+    );
+
+    let (route, least_minutes) = cave.find_fastest_route(&rules).unwrap().unwrap();
+
+    assert_eq!(least_minutes, 12);
+    assert!(route
+      .iter()
+      .any(|x| x.coord.x > first_box.max_x || x.coord.y > first_box.max_y));
+  }
+
+  #[test]
+  fn test_find_fastest_route_without_move_cost() {
+    let mut rules = RescueRules::default();
+    let mut cave = Cave::new(510, &Coord { x: 10, y: 10 });
+
+    rules.move_cost = 0;
+
+    assert!(cave.find_fastest_route(&rules).is_err());
+  }
+
+  #[test]
+  fn test_calc_least_minutes_to_target() {
+    let mut cave = Cave::new(510, &Coord { x: 10, y: 10 });
+    let least_minutes = cave.calc_least_minutes_to_target();
 
     assert_eq!(least_minutes, 45);
   }
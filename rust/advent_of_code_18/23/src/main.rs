@@ -85,13 +85,14 @@ manhattan distance between any of those points and 0,0,0?
 extern crate regex;
 
 use regex::Regex;
-use std::collections::HashMap;
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
 use std::fs::File;
 use std::io::prelude::*;
 
 type LengthUnit = i64;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
 struct Coord {
   x: LengthUnit,
   y: LengthUnit,
@@ -114,6 +115,51 @@ struct Boundary {
   min_z: LengthUnit,
 }
 
+// all the points with coordinates from `min` to `min + size - 1`
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+struct SearchCube {
+  min: Coord,
+  size: LengthUnit,
+  nanobots_num: usize,
+  distance_to_origin: LengthUnit,
+}
+
+impl SearchCube {
+  fn get_distance(&self, coord: &Coord) -> LengthUnit {
+    fn get_axis_distance(value: LengthUnit, min: LengthUnit, size: LengthUnit) -> LengthUnit {
+      if value < min {
+        min - value
+      } else if value > min + size - 1 {
+        value - (min + size - 1)
+      } else {
+        0
+      }
+    }
+
+    get_axis_distance(coord.x, self.min.x, self.size)
+      + get_axis_distance(coord.y, self.min.y, self.size)
+      + get_axis_distance(coord.z, self.min.z, self.size)
+  }
+}
+
+// the max heap pops the cube with most nanobots, then the closest to the origin, then the smallest
+impl Ord for SearchCube {
+  fn cmp(&self, other: &SearchCube) -> Ordering {
+    self
+      .nanobots_num
+      .cmp(&other.nanobots_num)
+      .then(other.distance_to_origin.cmp(&self.distance_to_origin))
+      .then(other.size.cmp(&self.size))
+      .then(other.min.cmp(&self.min))
+  }
+}
+
+impl PartialOrd for SearchCube {
+  fn partial_cmp(&self, other: &SearchCube) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
 #[derive(Debug, Clone, Copy)]
 struct NanoBot {
   coord: Coord,
@@ -186,168 +232,301 @@ impl NanoBot {
     boundary
   }
 
-  fn find_binary(
-    xs: &[LengthUnit],
-    ys: &[LengthUnit],
-    zs: &[LengthUnit],
-    forced_count: usize,
-    dist: LengthUnit,
-    nanobots: &[NanoBot],
-    offsets: &(LengthUnit, LengthUnit, LengthUnit),
-  ) -> (Option<LengthUnit>, Option<usize>) {
-    let mut at_target = vec![];
+  fn is_in_range_of_cube(&self, cube: &SearchCube) -> bool {
+    cube.get_distance(&self.coord) <= self.signal_radius
+  }
+
+  // branch and bound over an octree: every cube is ranked by the number of nanobots that reach
+  // any point of it (an upper bound for each of its points) and then by its distance to the
+  // origin (a lower bound), so the first cube of a single point to come out of the queue is the
+  // best point
+  fn get_best_point(nanobots: &[NanoBot]) -> (Coord, usize) {
+    let boundary = NanoBot::get_boundary_of_nanobots(nanobots);
     let orig_coord = Coord { x: 0, y: 0, z: 0 };
+    // the origin is included so the clamping of any point to the cube gets closer to it
+    let min = Coord {
+      x: std::cmp::min(boundary.min_x, 0),
+      y: std::cmp::min(boundary.min_y, 0),
+      z: std::cmp::min(boundary.min_z, 0),
+    };
+    let max_extent = *[
+      std::cmp::max(boundary.max_x, 0) - min.x,
+      std::cmp::max(boundary.max_y, 0) - min.y,
+      std::cmp::max(boundary.max_z, 0) - min.z,
+    ]
+    .iter()
+    .max()
+    .unwrap();
+    let mut size = 1;
+
+    while size <= max_extent {
+      size *= 2;
+    }
 
-    let min_xs = *xs.iter().min().unwrap();
-    let max_xs = *xs.iter().max().unwrap() + 1;
-    let min_ys = *ys.iter().min().unwrap();
-    let max_ys = *ys.iter().max().unwrap() + 1;
-    let min_zs = *zs.iter().min().unwrap();
-    let max_zs = *zs.iter().max().unwrap() + 1;
-
-    for x in (min_xs..=max_xs).step_by(dist as usize) {
-      for y in (min_ys..=max_ys).step_by(dist as usize) {
-        for z in (min_zs..=max_zs).step_by(dist as usize) {
-          // See how many bots are possible
-          let mut count = 0;
-          let coord = Coord { x, y, z };
+    let mut queue: BinaryHeap<SearchCube> = BinaryHeap::new();
+    let add_cube = |queue: &mut BinaryHeap<SearchCube>, min: Coord, size: LengthUnit| {
+      let mut cube = SearchCube {
+        min,
+        size,
+        nanobots_num: 0,
+        distance_to_origin: 0,
+      };
 
-          for bot in nanobots {
-            if dist == 1 {
-              if bot.coord.get_distance(&coord) <= bot.signal_radius {
-                count += 1;
-              }
-            } else {
-              let mut calc = ((offsets.0 + x) - (offsets.0 + bot.coord.x)).abs();
-              calc += ((offsets.1 + y) - (offsets.1 + bot.coord.y)).abs();
-              calc += ((offsets.2 + z) - (offsets.2 + bot.coord.z)).abs();
-
-              // The minus three is to include the current box
-              // in any bots that are near it
-              if (calc / dist) - 3 <= bot.signal_radius / dist {
-                count += 1
-              }
-            }
-          }
+      cube.nanobots_num = nanobots
+        .iter()
+        .filter(|nanobot| nanobot.is_in_range_of_cube(&cube))
+        .count();
+      cube.distance_to_origin = cube.get_distance(&orig_coord);
+
+      queue.push(cube);
+    };
+
+    add_cube(&mut queue, min, size);
+
+    while let Some(cube) = queue.pop() {
+      if cube.size == 1 {
+        return (cube.min, cube.nanobots_num);
+      }
+
+      let half = cube.size / 2;
+
+      for offset in 0..8 {
+        let child_min = Coord {
+          x: cube.min.x + if offset & 1 != 0 { half } else { 0 },
+          y: cube.min.y + if offset & 2 != 0 { half } else { 0 },
+          z: cube.min.z + if offset & 4 != 0 { half } else { 0 },
+        };
+
+        add_cube(&mut queue, child_min, half);
+      }
+    }
+
+    unreachable!()
+  }
+
+  fn overlaps(&self, other: &NanoBot) -> bool {
+    self.coord.get_distance(&other.coord) <= self.signal_radius + other.signal_radius
+  }
 
-          if count >= forced_count {
-            at_target.push((coord, count, coord.get_distance(&orig_coord)))
+  fn get_ids_in_range_of_coord(nanobots: &[NanoBot], coord: &Coord) -> Vec<usize> {
+    nanobots
+      .iter()
+      .filter(|nanobot| nanobot.coord.get_distance(coord) <= nanobot.signal_radius)
+      .map(|nanobot| nanobot.id)
+      .collect()
+  }
+
+  fn get_overlaps_matrix(nanobots: &[NanoBot]) -> Vec<Vec<bool>> {
+    nanobots
+      .iter()
+      .enumerate()
+      .map(|(idx_a, nanobot_a)| {
+        nanobots
+          .iter()
+          .enumerate()
+          .map(|(idx_b, nanobot_b)| idx_a != idx_b && nanobot_a.overlaps(nanobot_b))
+          .collect()
+      })
+      .collect()
+  }
+
+  // groups of nanobots connected by overlapping ranges, directly or through other nanobots
+  fn get_overlap_clusters(nanobots: &[NanoBot]) -> Vec<Vec<usize>> {
+    let overlaps = NanoBot::get_overlaps_matrix(nanobots);
+    let mut visited: Vec<bool> = vec![false; nanobots.len()];
+    let mut clusters: Vec<Vec<usize>> = vec![];
+
+    for start in 0..nanobots.len() {
+      if visited[start] {
+        continue;
+      }
+
+      let mut cluster: Vec<usize> = vec![];
+      let mut pending: Vec<usize> = vec![start];
+
+      visited[start] = true;
+
+      while let Some(idx) = pending.pop() {
+        cluster.push(nanobots[idx].id);
+
+        for (other_idx, overlap) in overlaps[idx].iter().enumerate() {
+          if *overlap && !visited[other_idx] {
+            visited[other_idx] = true;
+            pending.push(other_idx);
           }
         }
       }
+
+      cluster.sort();
+      clusters.push(cluster);
     }
 
-    while !at_target.is_empty() {
-      let mut best: Option<(Coord, usize, LengthUnit)> = None;
-      let mut best_i = None;
+    clusters
+  }
 
-      // Find the best candidate from the possible boxes
-      for (i, target) in at_target.iter().enumerate() {
-        if best_i.is_none() || target.2 < best.unwrap().2 {
-          best = Some(*target);
-          best_i = Some(i);
+  // Bron-Kerbosch with pivoting. In three dimensions the ranges of the clique overlap in pairs
+  // but they don't necessarily share a common point: for that use the nanobots in range of
+  // `get_best_point`
+  fn get_max_overlap_clique(nanobots: &[NanoBot]) -> Vec<usize> {
+    fn expand(
+      clique: &mut Vec<usize>,
+      candidates: Vec<usize>,
+      excluded: Vec<usize>,
+      overlaps: &[Vec<bool>],
+      best: &mut Vec<usize>,
+    ) {
+      if candidates.is_empty() && excluded.is_empty() {
+        if clique.len() > best.len() {
+          *best = clique.clone();
         }
+        return;
       }
 
-      if dist == 1 {
-        let (_, count, distance) = best.unwrap();
+      if clique.len() + candidates.len() <= best.len() {
+        return;
+      }
 
-        // At the end, just return the best match
-        return (Some(distance), Some(count));
-      } else {
-        let best_unwrapped = best.unwrap();
-        // Search in the sub boxes, see if we find any matches
-        let new_xs = vec![best_unwrapped.0.x, best_unwrapped.0.x + dist / 2];
-        let new_ys = vec![best_unwrapped.0.y, best_unwrapped.0.y + dist / 2];
-        let new_zs = vec![best_unwrapped.0.z, best_unwrapped.0.z + dist / 2];
-
-        let (a, b) = NanoBot::find_binary(
-          &new_xs,
-          &new_ys,
-          &new_zs,
-          forced_count,
-          dist / 2,
-          &nanobots,
-          &offsets,
-        );
-        if a.is_none() {
-          // This is a false path, remove it from consideration and try any others
-          at_target.remove(best_i.unwrap());
-        } else {
-          // We found something, go ahead and let it bubble up
-          return (a, b);
+      let pivot = *candidates
+        .iter()
+        .chain(excluded.iter())
+        .max_by_key(|u| candidates.iter().filter(|v| overlaps[**u][**v]).count())
+        .unwrap();
+      let mut candidates = candidates;
+      let mut excluded = excluded;
+
+      for v in candidates.clone() {
+        if overlaps[pivot][v] {
+          continue;
         }
+
+        clique.push(v);
+        expand(
+          clique,
+          candidates
+            .iter()
+            .cloned()
+            .filter(|u| overlaps[v][*u])
+            .collect(),
+          excluded
+            .iter()
+            .cloned()
+            .filter(|u| overlaps[v][*u])
+            .collect(),
+          overlaps,
+          best,
+        );
+        clique.pop();
+
+        candidates.retain(|u| *u != v);
+        excluded.push(v);
       }
     }
 
-    // This means all of the candidates yeild false paths, so let this one
-    // be treated as a false path by our caller
-    (None, None)
+    let overlaps = NanoBot::get_overlaps_matrix(nanobots);
+    let mut best: Vec<usize> = vec![];
+
+    expand(
+      &mut vec![],
+      (0..nanobots.len()).collect(),
+      vec![],
+      &overlaps,
+      &mut best,
+    );
+
+    let mut ids: Vec<usize> = best.iter().map(|idx| nanobots[*idx].id).collect();
+    ids.sort();
+
+    ids
   }
 
-  // considering to use the rust bindings for z3, but opted for porting:
-  // https://www.reddit.com/r/adventofcode/comments/a8s17l/2018_day_23_solutions/ecddus1
-  fn get_best_min_distance(nanobots: &[NanoBot]) -> LengthUnit {
-    let boundary = NanoBot::get_boundary_of_nanobots(&nanobots);
-    let mut dist = 1;
-    let nanobots_len = nanobots.len();
-
-    while dist < boundary.max_x - boundary.min_x
-      || dist < boundary.max_y - boundary.min_y
-      || dist < boundary.max_z - boundary.min_z
-    {
-      dist *= 2;
+  // A range is the intersection of the half-spaces `|f(p - c)| <= r` for the four forms:
+  // `x + y + z`, `x + y - z`, `x - y + z` and `-x + y + z`, so the intersection of ranges is a box
+  // in these forms. The points are the values of the last three forms that have the same parity
+  // and that add up to a value of the first form inside the box.
+  fn count_points_in_range_of_all(nanobots: &[NanoBot]) -> u128 {
+    fn get_forms(coord: &Coord) -> [i128; 4] {
+      let (x, y, z) = (coord.x as i128, coord.y as i128, coord.z as i128);
+
+      [x + y + z, x + y - z, x - y + z, -x + y + z]
     }
 
-    let xs: Vec<LengthUnit> = nanobots.iter().map(|n| n.coord.x).collect();
-    let xy: Vec<LengthUnit> = nanobots.iter().map(|n| n.coord.y).collect();
-    let xz: Vec<LengthUnit> = nanobots.iter().map(|n| n.coord.z).collect();
+    fn ceil_half(value: i128) -> i128 {
+      -(-value).div_euclid(2)
+    }
 
-    let offsets = (
-      boundary.min_x.abs(),
-      boundary.min_y.abs(),
-      boundary.min_z.abs(),
-    );
+    // number of non-negative triples adding up to `sum` or less
+    fn count_triples_up_to(sum: i128) -> i128 {
+      if sum < 0 {
+        return 0;
+      }
 
-    let mut span = 1;
-    while span < nanobots_len {
-      span *= 2;
+      (sum + 1) * (sum + 2) * (sum + 3) / 6
     }
 
-    let mut forced_check = 1;
-    let mut best_val: Option<LengthUnit> = None;
-    let mut best_count: Option<usize> = None;
-    let mut tried: HashMap<usize, (Option<LengthUnit>, Option<usize>)> = HashMap::new();
+    // triples inside `[0, lens)` adding up to `sum` or less, by inclusion-exclusion
+    fn count_triples_in_box_up_to(lens: &[i128; 3], sum: i128) -> i128 {
+      let mut total = 0;
 
-    loop {
-      if tried.get(&forced_check).is_none() {
-        let value = NanoBot::find_binary(&xs, &xy, &xz, forced_check, dist, &nanobots, &offsets);
-        tried.insert(forced_check, value);
-      }
-      let (test_val, test_count) = &tried[&forced_check];
+      for subset in 0..8 {
+        let mut shift = 0;
+        let mut sign = 1;
 
-      if test_val.is_none() {
-        if span > 1 {
-          span /= 2;
-        }
-        forced_check = std::cmp::max(1 as i32, forced_check as i32 - span as i32) as usize;
-      } else {
-        // We found something, so go forward
-        if best_count.is_none() || test_count.is_none() || test_count.unwrap() > best_count.unwrap()
-        {
-          best_val = *test_val;
-          best_count = *test_count;
+        for (idx, len) in lens.iter().enumerate() {
+          if subset & (1 << idx) != 0 {
+            shift += len;
+            sign = -sign;
+          }
         }
 
-        if span == 1 {
-          // This means we went back one, and it was empty, so we're done!
-          break;
-        }
-        forced_check += span;
+        total += sign * count_triples_up_to(sum - shift);
+      }
+
+      total
+    }
+
+    if nanobots.is_empty() {
+      return 0;
+    }
+
+    let mut mins: [i128; 4] = [i128::MIN; 4];
+    let mut maxs: [i128; 4] = [i128::MAX; 4];
+
+    for nanobot in nanobots {
+      let forms = get_forms(&nanobot.coord);
+      let radius = nanobot.signal_radius as i128;
+
+      for idx in 0..4 {
+        mins[idx] = std::cmp::max(mins[idx], forms[idx] - radius);
+        maxs[idx] = std::cmp::min(maxs[idx], forms[idx] + radius);
       }
     }
 
-    best_val.unwrap()
+    let mut total = 0;
+
+    for parity in 0..2 {
+      let mut lens: [i128; 3] = [0; 3];
+      let mut offset = 0;
+
+      for idx in 0..3 {
+        let min = ceil_half(mins[idx + 1] - parity);
+        let max = (maxs[idx + 1] - parity).div_euclid(2);
+
+        lens[idx] = std::cmp::max(0, max - min + 1);
+        offset += min;
+      }
+
+      let min_sum = ceil_half(mins[0] - 3 * parity) - offset;
+      let max_sum = (maxs[0] - 3 * parity).div_euclid(2) - offset;
+
+      if lens.contains(&0) || min_sum > max_sum {
+        continue;
+      }
+
+      total +=
+        count_triples_in_box_up_to(&lens, max_sum) - count_triples_in_box_up_to(&lens, min_sum - 1);
+    }
+
+    total as u128
   }
 }
 
@@ -364,11 +543,34 @@ fn get_input_nanobots() -> Vec<NanoBot> {
 fn main() {
   let input_nanobots = get_input_nanobots();
   let num_nanobots = NanoBot::get_nanobots_num_in_range(&input_nanobots);
-  let best_min_distance = NanoBot::get_best_min_distance(&input_nanobots);
+  let (best_point, best_point_nanobots) = NanoBot::get_best_point(&input_nanobots);
+  let best_min_distance = best_point.get_distance(&Coord { x: 0, y: 0, z: 0 });
+  let best_point_ids = NanoBot::get_ids_in_range_of_coord(&input_nanobots, &best_point);
+  let best_point_group: Vec<NanoBot> = input_nanobots
+    .iter()
+    .filter(|nanobot| best_point_ids.contains(&nanobot.id))
+    .cloned()
+    .collect();
+  let clusters = NanoBot::get_overlap_clusters(&input_nanobots);
+  let max_clique = NanoBot::get_max_overlap_clique(&input_nanobots);
 
   println!("Results:");
   println!("- (1) nanobots in range: {}", num_nanobots);
   println!("- (2) best min distance: {}", best_min_distance);
+  println!(
+    "- best point: {},{},{} (in range of {} nanobots)",
+    best_point.x, best_point.y, best_point.z, best_point_nanobots
+  );
+  println!(
+    "- points in range of all of them: {}",
+    NanoBot::count_points_in_range_of_all(&best_point_group)
+  );
+  println!(
+    "- overlap clusters: {}, biggest: {}",
+    clusters.len(),
+    clusters.iter().map(|x| x.len()).max().unwrap()
+  );
+  println!("- max overlap clique: {}", max_clique.len());
 }
 
 #[cfg(test)]
@@ -416,8 +618,198 @@ pos=<10,10,10>, r=5",
   #[test]
   fn test_get_best_min_distance() {
     let nanobots = get_example_data_2();
-    let best_min_distance = NanoBot::get_best_min_distance(&nanobots);
+    let best_point = NanoBot::get_best_point(&nanobots);
+
+    assert_eq!(best_point.0.get_distance(&Coord { x: 0, y: 0, z: 0 }), 36);
+    assert_eq!(
+      best_point,
+      (
+        Coord {
+          x: 12,
+          y: 12,
+          z: 12
+        },
+        5
+      )
+    );
+  }
+
+  #[test]
+  fn test_get_ids_in_range_of_coord() {
+    let nanobots = get_example_data_2();
+
+    assert_eq!(
+      NanoBot::get_ids_in_range_of_coord(
+        &nanobots,
+        &Coord {
+          x: 12,
+          y: 12,
+          z: 12
+        }
+      ),
+      vec![0, 1, 2, 3, 4]
+    );
+    assert_eq!(
+      NanoBot::get_ids_in_range_of_coord(&nanobots, &Coord { x: 300, y: 0, z: 0 }),
+      vec![]
+    );
+  }
+
+  #[test]
+  fn test_get_overlap_clusters() {
+    let nanobots = NanoBot::new_from_str(
+      "pos=<0,0,0>, r=1
+pos=<3,0,0>, r=2
+pos=<6,0,0>, r=1
+pos=<20,0,0>, r=1
+pos=<0,30,0>, r=1
+pos=<0,30,2>, r=1",
+    );
+
+    assert_eq!(
+      NanoBot::get_overlap_clusters(&nanobots),
+      vec![vec![0, 1, 2], vec![3], vec![4, 5]]
+    );
+  }
+
+  #[test]
+  fn test_get_max_overlap_clique() {
+    let nanobots = get_example_data_2();
+
+    assert_eq!(
+      NanoBot::get_max_overlap_clique(&nanobots),
+      vec![0, 1, 2, 3, 4]
+    );
+  }
 
-    assert_eq!(best_min_distance, 36);
+  #[test]
+  fn test_count_points_in_range_of_all() {
+    let nanobots = NanoBot::new_from_str(
+      "pos=<0,0,0>, r=1
+pos=<5,5,5>, r=2
+pos=<0,0,1>, r=3",
+    );
+
+    assert_eq!(NanoBot::count_points_in_range_of_all(&nanobots[0..1]), 7);
+    assert_eq!(NanoBot::count_points_in_range_of_all(&nanobots[1..2]), 25);
+    assert_eq!(NanoBot::count_points_in_range_of_all(&nanobots[0..2]), 0);
+    assert_eq!(
+      NanoBot::count_points_in_range_of_all(&[nanobots[0], nanobots[2]]),
+      7
+    );
+  }
+
+  // small xorshift generator, so the cases are random but reproducible
+  struct RandomNumbers {
+    state: u64,
+  }
+
+  impl RandomNumbers {
+    fn next_in_range(&mut self, min: LengthUnit, max: LengthUnit) -> LengthUnit {
+      self.state ^= self.state << 13;
+      self.state ^= self.state >> 7;
+      self.state ^= self.state << 17;
+
+      min + (self.state % (max - min + 1) as u64) as LengthUnit
+    }
+  }
+
+  fn get_best_point_brute_force(nanobots: &[NanoBot]) -> (usize, LengthUnit) {
+    let orig_coord = Coord { x: 0, y: 0, z: 0 };
+    let mut best: (usize, LengthUnit) = (0, 0);
+
+    for x in -18..=18 {
+      for y in -18..=18 {
+        for z in -18..=18 {
+          let coord = Coord { x, y, z };
+          let count = nanobots
+            .iter()
+            .filter(|nanobot| nanobot.coord.get_distance(&coord) <= nanobot.signal_radius)
+            .count();
+          let distance = coord.get_distance(&orig_coord);
+
+          if count > best.0 || (count == best.0 && distance < best.1) {
+            best = (count, distance);
+          }
+        }
+      }
+    }
+
+    best
+  }
+
+  #[test]
+  fn test_get_best_point_against_brute_force() {
+    let mut random_numbers = RandomNumbers {
+      state: 0x2545_f491_4f6c_dd1d,
+    };
+
+    for _ in 0..40 {
+      let nanobots_num = random_numbers.next_in_range(1, 8) as usize;
+      let nanobots: Vec<NanoBot> = (0..nanobots_num)
+        .map(|id| NanoBot {
+          coord: Coord {
+            x: random_numbers.next_in_range(-10, 10),
+            y: random_numbers.next_in_range(-10, 10),
+            z: random_numbers.next_in_range(-10, 10),
+          },
+          signal_radius: random_numbers.next_in_range(0, 8),
+          id,
+        })
+        .collect();
+
+      let (coord, count) = NanoBot::get_best_point(&nanobots);
+      let (expected_count, expected_distance) = get_best_point_brute_force(&nanobots);
+
+      assert_eq!(count, expected_count, "{:?}", nanobots);
+      assert_eq!(
+        coord.get_distance(&Coord { x: 0, y: 0, z: 0 }),
+        expected_distance,
+        "{:?}",
+        nanobots
+      );
+    }
+  }
+
+  #[test]
+  fn test_count_points_in_range_of_all_against_brute_force() {
+    let mut random_numbers = RandomNumbers {
+      state: 0x9e37_79b9_7f4a_7c15,
+    };
+
+    for _ in 0..40 {
+      let nanobots_num = random_numbers.next_in_range(1, 3) as usize;
+      let nanobots: Vec<NanoBot> = (0..nanobots_num)
+        .map(|id| NanoBot {
+          coord: Coord {
+            x: random_numbers.next_in_range(-4, 4),
+            y: random_numbers.next_in_range(-4, 4),
+            z: random_numbers.next_in_range(-4, 4),
+          },
+          signal_radius: random_numbers.next_in_range(0, 7),
+          id,
+        })
+        .collect();
+      let mut expected = 0;
+
+      for x in -12..=12 {
+        for y in -12..=12 {
+          for z in -12..=12 {
+            let coord = Coord { x, y, z };
+
+            if NanoBot::get_ids_in_range_of_coord(&nanobots, &coord).len() == nanobots_num {
+              expected += 1;
+            }
+          }
+        }
+      }
+
+      assert_eq!(
+        NanoBot::count_points_in_range_of_all(&nanobots),
+        expected,
+        "{:?}",
+        nanobots
+      );
+    }
   }
 }
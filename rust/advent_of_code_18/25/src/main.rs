@@ -125,112 +125,217 @@ You have enough stars to .
 
 */
 
-extern crate regex;
-
-use regex::Regex;
 use std::collections::HashMap;
-use std::collections::HashSet;
 use std::fs::File;
 use std::io::prelude::*;
 
 type LengthUnit = i64;
 
+// from this number of points, the constellations are found comparing only the points of
+// contiguous buckets instead of every pair
+const BUCKETS_MIN_POINTS: usize = 200;
+
 #[derive(Debug, Eq, PartialEq)]
 struct Point {
   id: usize,
-  x: LengthUnit,
-  y: LengthUnit,
-  z: LengthUnit,
-  t: LengthUnit,
+  coords: Vec<LengthUnit>,
 }
 
-impl Point {
-  fn calc_distance(&self, other: &Point) -> LengthUnit {
-    (self.x - other.x).abs()
-      + (self.y - other.y).abs()
-      + (self.z - other.z).abs()
-      + (self.t - other.t).abs()
+struct DisjointSet {
+  parents: Vec<usize>,
+  ranks: Vec<usize>,
+}
+
+impl DisjointSet {
+  fn new(size: usize) -> Self {
+    DisjointSet {
+      parents: (0..size).collect(),
+      ranks: vec![0; size],
+    }
   }
 
-  fn new_from_text(text: &str) -> Vec<Point> {
-    let reg = Regex::new(r"^(.+),(.+),(.+),(.+)$").unwrap();
-    let mut points = vec![];
+  fn find(&mut self, item: usize) -> usize {
+    let mut root = item;
 
-    for (idx, line) in text.lines().enumerate() {
-      let caps = reg.captures(line).unwrap();
+    while self.parents[root] != root {
+      root = self.parents[root];
+    }
 
-      let point = Point {
-        id: idx,
-        x: caps.get(1).unwrap().as_str().parse::<LengthUnit>().unwrap(),
-        y: caps.get(2).unwrap().as_str().parse::<LengthUnit>().unwrap(),
-        z: caps.get(3).unwrap().as_str().parse::<LengthUnit>().unwrap(),
-        t: caps.get(4).unwrap().as_str().parse::<LengthUnit>().unwrap(),
-      };
+    let mut current = item;
 
-      points.push(point);
+    while self.parents[current] != root {
+      let next = self.parents[current];
+      self.parents[current] = root;
+      current = next;
     }
 
-    points
+    root
   }
 
-  fn calc_constelations_num(points: &[Point]) -> usize {
-    let mut graph: HashMap<usize, HashSet<usize>> = HashMap::new();
+  fn union(&mut self, item_a: usize, item_b: usize) {
+    let root_a = self.find(item_a);
+    let root_b = self.find(item_b);
 
-    for point in points {
-      graph.entry(point.id).or_insert_with(HashSet::new);
+    if root_a == root_b {
+      return;
+    }
 
-      for point2 in points {
-        if point2.id == point.id {
-          continue;
-        }
+    if self.ranks[root_a] < self.ranks[root_b] {
+      self.parents[root_a] = root_b;
+    } else if self.ranks[root_a] > self.ranks[root_b] {
+      self.parents[root_b] = root_a;
+    } else {
+      self.parents[root_b] = root_a;
+      self.ranks[root_a] += 1;
+    }
+  }
 
-        if point.calc_distance(&point2) <= 3 {
-          {
-            let mut entry = graph.entry(point.id).or_insert_with(HashSet::new);
-            entry.insert(point2.id);
-          }
+  fn get_groups(&mut self) -> Vec<Vec<usize>> {
+    let mut root_to_group: HashMap<usize, usize> = HashMap::new();
+    let mut groups: Vec<Vec<usize>> = vec![];
 
-          {
-            let mut entry = graph.entry(point2.id).or_insert_with(HashSet::new);
-            entry.insert(point.id);
-          }
-        }
+    for item in 0..self.parents.len() {
+      let root = self.find(item);
+      let group_idx = *root_to_group.entry(root).or_insert_with(|| {
+        groups.push(vec![]);
+        groups.len() - 1
+      });
+
+      groups[group_idx].push(item);
+    }
+
+    groups
+  }
+}
+
+impl Point {
+  fn calc_distance(&self, other: &Point) -> LengthUnit {
+    self
+      .coords
+      .iter()
+      .zip(other.coords.iter())
+      .map(|(a, b)| (a - b).abs())
+      .sum()
+  }
+
+  // each line is a point, with any number of dimensions (the same for all of them)
+  fn new_from_text(text: &str) -> Vec<Point> {
+    let mut points: Vec<Point> = vec![];
+
+    for (idx, line) in text
+      .lines()
+      .map(|x| x.trim())
+      .filter(|x| !x.is_empty())
+      .enumerate()
+    {
+      let coords: Vec<LengthUnit> = line
+        .split(',')
+        .map(|x| x.trim().parse::<LengthUnit>().unwrap())
+        .collect();
+
+      if let Some(first) = points.first() {
+        assert_eq!(
+          first.coords.len(),
+          coords.len(),
+          "Invalid dimensions in line: {}",
+          line
+        );
       }
+
+      points.push(Point { id: idx, coords });
     }
 
-    let mut constellations_num = 0;
-    let mut covered_points: HashSet<usize> = HashSet::new();
+    points
+  }
 
-    for point in points {
-      if covered_points.contains(&point.id) {
-        continue;
+  fn get_groups_ids(points: &[Point], mut disjoint_set: DisjointSet) -> Vec<Vec<usize>> {
+    disjoint_set
+      .get_groups()
+      .iter()
+      .map(|group| group.iter().map(|idx| points[*idx].id).collect())
+      .collect()
+  }
+
+  fn find_constellations_pairwise(points: &[Point], max_distance: LengthUnit) -> Vec<Vec<usize>> {
+    let mut disjoint_set = DisjointSet::new(points.len());
+
+    for (idx_a, point_a) in points.iter().enumerate() {
+      for (idx_b, point_b) in points.iter().enumerate().skip(idx_a + 1) {
+        if point_a.calc_distance(point_b) <= max_distance {
+          disjoint_set.union(idx_a, idx_b);
+        }
       }
+    }
 
-      let mut points_to_cover: HashSet<usize> = HashSet::new();
+    Point::get_groups_ids(points, disjoint_set)
+  }
+
+  // the buckets have the side of the max distance, so two linked points are always in the same
+  // or in contiguous buckets (3 ^ dimensions of them)
+  fn find_constellations_with_buckets(
+    points: &[Point],
+    max_distance: LengthUnit,
+  ) -> Vec<Vec<usize>> {
+    let bucket_size = std::cmp::max(max_distance, 1);
+    let mut buckets: HashMap<Vec<LengthUnit>, Vec<usize>> = HashMap::new();
+    let mut disjoint_set = DisjointSet::new(points.len());
+
+    for (idx, point) in points.iter().enumerate() {
+      let bucket: Vec<LengthUnit> = point
+        .coords
+        .iter()
+        .map(|x| x.div_euclid(bucket_size))
+        .collect();
+
+      buckets.entry(bucket).or_default().push(idx);
+    }
 
-      points_to_cover.insert(point.id);
+    let dimensions = points.first().map(|x| x.coords.len()).unwrap_or(0);
+    let offsets_num = 3_usize.pow(dimensions as u32);
+
+    for (bucket, bucket_points) in buckets.iter() {
+      for offset_idx in 0..offsets_num {
+        let mut remaining = offset_idx;
+        let contiguous_bucket: Vec<LengthUnit> = bucket
+          .iter()
+          .map(|x| {
+            let offset = (remaining % 3) as LengthUnit - 1;
+            remaining /= 3;
+            x + offset
+          })
+          .collect();
+
+        // each pair of buckets is only compared once
+        if contiguous_bucket < *bucket {
+          continue;
+        }
 
-      while !points_to_cover.is_empty() {
-        for point_to_cover in points_to_cover.clone() {
-          let connected_points = graph[&point_to_cover].clone();
+        let other_points = match buckets.get(&contiguous_bucket) {
+          Some(other_points) => other_points,
+          None => continue,
+        };
 
-          for connected_point in connected_points {
-            if covered_points.contains(&connected_point) {
-              continue;
-            } else {
-              points_to_cover.insert(connected_point);
+        for idx_a in bucket_points {
+          for idx_b in other_points {
+            let is_same_pair = contiguous_bucket == *bucket && idx_a >= idx_b;
+
+            if !is_same_pair && points[*idx_a].calc_distance(&points[*idx_b]) <= max_distance {
+              disjoint_set.union(*idx_a, *idx_b);
             }
           }
-
-          points_to_cover.remove(&point_to_cover);
-          covered_points.insert(point_to_cover);
         }
       }
+    }
 
-      constellations_num += 1;
+    Point::get_groups_ids(points, disjoint_set)
+  }
+
+  fn find_constellations(points: &[Point], max_distance: LengthUnit) -> Vec<Vec<usize>> {
+    if points.len() < BUCKETS_MIN_POINTS {
+      return Point::find_constellations_pairwise(points, max_distance);
     }
 
-    constellations_num
+    Point::find_constellations_with_buckets(points, max_distance)
   }
 }
 
@@ -246,10 +351,12 @@ fn get_input_points() -> Vec<Point> {
 
 fn main() {
   let points = get_input_points();
-  let constellations = Point::calc_constelations_num(&points);
+  let constellations = Point::find_constellations(&points, 3);
+  let biggest_constellation = constellations.iter().map(|x| x.len()).max().unwrap();
 
   println!("Results:");
-  println!("- (1) constellations number: {}", constellations);
+  println!("- (1) constellations number: {}", constellations.len());
+  println!("- biggest constellation: {} points", biggest_constellation);
 }
 
 #[cfg(test)]
@@ -259,8 +366,9 @@ mod tests {
   #[test]
   fn test_calc_constelations_num() {
     assert_eq!(
-      Point::calc_constelations_num(&Point::new_from_text(
-        "0,0,0,0
+      Point::find_constellations(
+        &Point::new_from_text(
+          "0,0,0,0
 3,0,0,0
 0,3,0,0
 0,0,3,0
@@ -268,12 +376,16 @@ mod tests {
 0,0,0,6
 9,0,0,0
 12,0,0,0",
-      )),
+        ),
+        3
+      )
+      .len(),
       2
     );
     assert_eq!(
-      Point::calc_constelations_num(&Point::new_from_text(
-        "-1,2,2,0
+      Point::find_constellations(
+        &Point::new_from_text(
+          "-1,2,2,0
 0,0,2,-2
 0,0,0,-2
 -1,2,0,0
@@ -283,12 +395,16 @@ mod tests {
 -1,0,-1,0
 0,2,1,-2
 3,0,0,0"
-      )),
+        ),
+        3
+      )
+      .len(),
       4
     );
     assert_eq!(
-      Point::calc_constelations_num(&Point::new_from_text(
-        "1,-1,0,1
+      Point::find_constellations(
+        &Point::new_from_text(
+          "1,-1,0,1
 2,0,-1,0
 3,2,-1,0
 0,0,3,1
@@ -298,12 +414,16 @@ mod tests {
 2,-2,0,-1
 1,-1,0,-1
 3,2,0,2"
-      )),
+        ),
+        3
+      )
+      .len(),
       3
     );
     assert_eq!(
-      Point::calc_constelations_num(&Point::new_from_text(
-        "1,-1,-1,-2
+      Point::find_constellations(
+        &Point::new_from_text(
+          "1,-1,-1,-2
 -2,-2,0,1
 0,2,1,3
 -2,3,-2,1
@@ -313,8 +433,81 @@ mod tests {
 -2,2,3,-1
 1,2,2,0
 -1,-2,0,-2"
-      )),
+        ),
+        3
+      )
+      .len(),
       8
     );
   }
+
+  #[test]
+  fn test_find_constellations_members() {
+    let points = Point::new_from_text(
+      "0,0,0,0
+3,0,0,0
+0,3,0,0
+0,0,3,0
+0,0,0,3
+0,0,0,6
+9,0,0,0
+12,0,0,0",
+    );
+
+    assert_eq!(
+      Point::find_constellations(&points, 3),
+      vec![vec![0, 1, 2, 3, 4, 5], vec![6, 7]]
+    );
+    assert_eq!(Point::find_constellations(&points, 2).len(), 8);
+    assert_eq!(Point::find_constellations(&points, 6).len(), 1);
+  }
+
+  #[test]
+  fn test_find_constellations_other_dimensions() {
+    let points = Point::new_from_text(
+      "0,0
+1,1
+5,5
+5,6
+10,0",
+    );
+
+    assert_eq!(
+      Point::find_constellations(&points, 2),
+      vec![vec![0, 1], vec![2, 3], vec![4]]
+    );
+
+    let points = Point::new_from_text("1\n4\n-3\n8");
+
+    assert_eq!(
+      Point::find_constellations(&points, 4),
+      vec![vec![0, 1, 2, 3]]
+    );
+  }
+
+  #[test]
+  fn test_find_constellations_with_buckets() {
+    let mut text: Vec<String> = vec![];
+    let mut seed: i64 = 7;
+
+    for _ in 0..300 {
+      let mut coords: Vec<String> = vec![];
+
+      for _ in 0..4 {
+        seed = (seed * 1_103_515_245 + 12_345) % 2_147_483_648;
+        coords.push((seed % 21 - 10).to_string());
+      }
+
+      text.push(coords.join(","));
+    }
+
+    let points = Point::new_from_text(&text.join("\n"));
+
+    for max_distance in 0..6 {
+      assert_eq!(
+        Point::find_constellations_with_buckets(&points, max_distance),
+        Point::find_constellations_pairwise(&points, max_distance)
+      );
+    }
+  }
 }